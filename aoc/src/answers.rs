@@ -0,0 +1,129 @@
+//! Confirmed answers, kept in `answers.txt` under the puzzle root so `aoc verify` can check the
+//! solvers still produce them.
+//!
+//! Each line is `<day> <part> <input> <answer>`, where the input is the name the runner gives it
+//! (`input`, `example`, or a file stem). Drawn answers span several lines, so line breaks are
+//! written as `\n` (and backslashes as `\\`). Blank lines and lines starting with `#` are ignored.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input;
+
+const HEADER: &str = "\
+# confirmed answers, checked by `aoc verify` and updated by `aoc record`
+# <day> <part> <input> <answer>
+";
+
+type Key = (u8, u8, String);
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<Key, String>,
+}
+
+pub fn path() -> PathBuf {
+    input::root().join("answers.txt")
+}
+
+impl Answers {
+    /// Reads the answers file, which doesn't have to exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(format!("can't read {}: {err}", path.display())),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad = || format!("line {}: expected <day> <part> <input> <answer>", i + 1);
+            let mut fields = line.splitn(4, ' ');
+            let mut field = || fields.next().filter(|f| !f.is_empty()).ok_or_else(bad);
+            let day = field()?.parse().map_err(|_| bad())?;
+            let part = field()?.parse().map_err(|_| bad())?;
+            let input = field()?.to_string();
+            let answer = unescape(field()?.trim());
+            entries.insert((day, part, input), answer);
+        }
+        Ok(Answers { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut text = HEADER.to_string();
+        for ((day, part, input), answer) in &self.entries {
+            text += &format!("{day} {part} {input} {}\n", escape(answer));
+        }
+        fs::write(path, text).map_err(|err| format!("can't write {}: {err}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.entries
+            .get(&(day, part, input.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Stores `answer`, returning the one it replaces.
+    pub fn record(&mut self, day: u8, part: u8, input: &str, answer: &str) -> Option<String> {
+        self.entries
+            .insert((day, part, input.to_string()), answer.to_string())
+    }
+}
+
+pub fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut answer = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                answer.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                answer.push('\\');
+                chars.next();
+            }
+            _ => answer.push(c),
+        }
+    }
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, unescape, Answers};
+
+    #[test]
+    fn drawn_answers_survive_a_round_trip() {
+        let answer = "#..#\n#..#\n\\..\\";
+        assert_eq!(escape(answer), "#..#\\n#..#\\n\\\\..\\\\");
+        assert_eq!(unescape(&escape(answer)), answer);
+    }
+
+    #[test]
+    fn parse_skips_comments_and_keeps_the_rest_of_the_line() {
+        let answers =
+            Answers::parse("# header\n\n20 1 input 7153\n21 2 example not solved\n").unwrap();
+        assert_eq!(answers.get(20, 1, "input"), Some("7153"));
+        assert_eq!(answers.get(21, 2, "example"), Some("not solved"));
+        assert_eq!(answers.get(20, 2, "input"), None);
+    }
+
+    #[test]
+    fn parse_rejects_short_lines() {
+        let err = Answers::parse("20 1 input\n").unwrap_err();
+        assert_eq!(err, "line 1: expected <day> <part> <input> <answer>");
+    }
+}
@@ -0,0 +1,185 @@
+//! `aoc bench`: times parsing and each part separately over repeated runs.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::cli::RunArgs;
+use crate::{input, json};
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub run: RunArgs,
+    pub runs: usize,
+    pub out: PathBuf,
+}
+
+impl Default for BenchArgs {
+    fn default() -> Self {
+        BenchArgs {
+            run: RunArgs::default(),
+            runs: 5,
+            out: PathBuf::from("bench.json"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    fn of(times: &mut [Duration]) -> Stats {
+        times.sort();
+        let mid = times.len() / 2;
+        let median = if times.len().is_multiple_of(2) {
+            (times[mid - 1] + times[mid]) / 2
+        } else {
+            times[mid]
+        };
+        Stats {
+            min: times[0],
+            median,
+            max: times[times.len() - 1],
+        }
+    }
+}
+
+struct Timing {
+    day: u8,
+    input: String,
+    phase: &'static str,
+    stats: Stats,
+}
+
+impl Timing {
+    fn to_json(&self) -> String {
+        json::object(&[
+            ("day", self.day.to_string()),
+            ("input", json::string(&self.input)),
+            ("phase", json::string(self.phase)),
+            ("min_ns", self.stats.min.as_nanos().to_string()),
+            ("median_ns", self.stats.median.as_nanos().to_string()),
+            ("max_ns", self.stats.max.as_nanos().to_string()),
+        ])
+    }
+}
+
+// runs `f` `runs` times, keeping the last result
+fn time<T>(runs: usize, mut f: impl FnMut() -> T) -> (T, Stats) {
+    let mut times = Vec::with_capacity(runs);
+    let mut result = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        result = Some(f());
+        times.push(start.elapsed());
+    }
+    (result.expect("at least one run"), Stats::of(&mut times))
+}
+
+fn show(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{secs:.2}s")
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.2}µs", secs * 1e6)
+    }
+}
+
+// so results from different commits can be told apart
+fn commit() -> Option<String> {
+    let out = process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(input::root())
+        .output()
+        .ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+pub fn bench(args: BenchArgs) -> Result<(), String> {
+    println!(
+        "{:>3}  {:<10}  {:<6}  {:>10}  {:>10}  {:>10}",
+        "day", "input", "phase", "min", "median", "max"
+    );
+    let config = crate::config(&args.run)?;
+    let mut timings = vec![];
+    for day in crate::days(args.run.day)? {
+        let input = input::load(day, &args.run.source).map_err(|e| e.to_string())?;
+        let (parsed, stats) = time(args.runs, || crate::parse(day, &input, &config));
+        let parsed = parsed?;
+        let mut phases = vec![("parse", stats)];
+        for part in crate::parts(args.run.part) {
+            let phase = if part == 1 { "part 1" } else { "part 2" };
+            phases.push((phase, time(args.runs, || parsed.solve(part)).1));
+        }
+        for (phase, stats) in phases {
+            println!(
+                "{day:>3}  {:<10}  {phase:<6}  {:>10}  {:>10}  {:>10}",
+                input.name,
+                show(stats.min),
+                show(stats.median),
+                show(stats.max)
+            );
+            timings.push(Timing {
+                day,
+                input: input.name.clone(),
+                phase,
+                stats,
+            });
+        }
+    }
+
+    let timings: Vec<String> = timings
+        .iter()
+        .map(|t| format!("    {}", t.to_json()))
+        .collect();
+    let report = format!(
+        "{{\n  \"commit\": {},\n  \"runs\": {},\n  \"timings\": [\n{}\n  ]\n}}\n",
+        commit().map_or("null".to_string(), |c| json::string(&c)),
+        args.runs,
+        timings.join(",\n")
+    );
+    fs::write(&args.out, report)
+        .map_err(|err| format!("can't write {}: {err}", args.out.display()))?;
+    println!("wrote {}", args.out.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{show, Stats};
+    use std::time::Duration;
+
+    #[test]
+    fn stats_are_taken_from_the_sorted_times() {
+        let mut times = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        let stats = Stats::of(&mut times);
+        assert_eq!(
+            (stats.min, stats.median, stats.max),
+            (
+                Duration::from_millis(1),
+                Duration::from_millis(3),
+                Duration::from_millis(5)
+            )
+        );
+
+        let mut times = [4, 1].map(Duration::from_millis);
+        let stats = Stats::of(&mut times);
+        assert_eq!(stats.median, Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn durations_are_shown_in_a_sensible_unit() {
+        assert_eq!(show(Duration::from_micros(1500)), "1.50ms");
+        assert_eq!(show(Duration::from_nanos(2500)), "2.50µs");
+        assert_eq!(show(Duration::from_millis(12_340)), "12.34s");
+    }
+}
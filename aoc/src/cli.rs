@@ -0,0 +1,361 @@
+// hand-rolled argument parsing - the command line is small enough that it's not worth a dependency
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_common::image::Export;
+use aoc_common::Params;
+
+use crate::bench::BenchArgs;
+use crate::crosscheck::CrosscheckArgs;
+use crate::input::Source;
+use crate::report::Format;
+
+pub const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+    run       solve puzzles and print the answers
+    verify    solve puzzles and check the answers against answers.txt
+    record    solve puzzles and save the answers to answers.txt, once they're confirmed
+    bench     time parsing and each part over repeated runs
+    crosscheck
+              solve puzzles with the Python solutions too (days 1, 19 and 21) and compare
+    fetch     download a day's input to dayN/input.txt (needs --day)
+    generate  make up a random input for a day and print it (needs --day)
+    submit    solve one part and send the answer to the site (needs --day and --part)
+    help      print this message
+
+Options for every command:
+    -v, --verbose     show debug output from the solvers; -vv for trace output too
+                      (or set $AOC_LOG to warn, info, debug or trace)
+
+Options for run, verify, record, bench, crosscheck and submit:
+    -d, --day <N>     only run day N (default: every day)
+    -p, --part <P>    only run part P, 1 or 2 (default: both)
+    -e, --example     use the example from the puzzle text (dayN/test.input.txt)
+    [PATH | -]        read the input from PATH, or from stdin for -, instead of dayN/input.txt
+                      (needs --day)
+
+Options for run and verify:
+    --format <F>      text (the default) or json: a JSON object per line for each part, with its
+                      day, part, input, status, answer and timings
+
+Options for run and bench:
+    --param <KEY=VALUE>
+                      set one of the day's puzzle parameters, in place of its value from
+                      aoc.toml (needs --day; can be given more than once)
+
+Options for run:
+    --visualize       draw the state of the simulations (days 10, 14, 17 and 22) on stderr
+    --fps <N>         animate them too, at N frames a second (implies --visualize)
+    --export <DIR>    save pictures of the simulations (days 14, 15, 17 and 22) in DIR
+    --frames          with --export, save every frame of the animations too, numbered
+    --scale <N>       with --export, draw each cell as N by N pixels (default: 4)
+    --palette <COLOURS>
+                      with --export, comma separated rrggbb colours to use in place of the
+                      day's own, in the order it lists them
+    --image-format <F>
+                      with --export, png (the default) or ppm
+
+verify and crosscheck check both dayN/input.txt and the example unless told which input to use.
+record needs --day.
+
+Puzzle parameters that aren't part of the input (the row day 15 checks, how many rocks fall on
+day 17, ...) are read from aoc.toml under the puzzle root: a [dayN] section sets them for every
+input of day N, and a [dayN.NAME] section for the input named NAME (input, example, or a file's
+stem). A day's own values for its real input are used for any that aren't set.
+
+Options for bench:
+    -n, --runs <N>    how many times to run each phase (default: 5)
+    -o, --out <FILE>  where to write the timings as JSON (default: bench.json)
+
+Options for crosscheck:
+    --timeout <SECS>  how long to give each Python run before giving up on it (default: 60)
+
+crosscheck runs the Python solution with $AOC_PYTHON (default: python3) from its day's folder,
+giving it the input on stdin. The Python solutions have their puzzle parameters written in, so
+check aoc.toml has the same ones for the input.
+
+Options for generate:
+    -s, --seed <N>    seed for the random choices; the same seed and size give the same input
+                      (default: 0)
+    --size <N>        how big to make it, in the day's own terms (elves, valves, cubes, ...;
+                      default: about as big as a real input)
+
+fetch reads the session token from $AOC_SESSION or .session, downloads from $AOC_BASE_URL
+(default: https://adventofcode.com/2022), and keeps every input it downloads in $AOC_CACHE
+(default: .cache) so it never downloads one twice. https URLs are fetched with curl, which has to
+be installed.
+
+submit uses the same session token and base URL. It keeps what the site said in
+.submissions.txt, won't send an answer that's already been turned down (or is past a known too
+high or too low), and saves accepted answers to answers.txt.
+
+The dayN folders are looked up under $AOC_ROOT, or the workspace aoc was built from.
+";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Verify(RunArgs),
+    Record(RunArgs),
+    Bench(BenchArgs),
+    Crosscheck(CrosscheckArgs),
+    Fetch(u8),
+    Generate(GenerateArgs),
+    Submit(RunArgs),
+    Help,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RunArgs {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub source: Source,
+    pub visualize: bool,
+    pub fps: Option<u32>,
+    pub export: Export,
+    pub params: Params,
+    pub format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateArgs {
+    pub day: u8,
+    pub seed: u64,
+    pub size: Option<usize>,
+}
+
+/// Parses the arguments after the program name, returning the command and how many times
+/// `-v` was given.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<(Command, u8), String> {
+    let mut verbosity = 0;
+    let args: Vec<String> = args
+        .into_iter()
+        .filter(|arg| match arg.as_str() {
+            "-v" | "--verbose" => {
+                verbosity += 1;
+                false
+            }
+            "-vv" => {
+                verbosity += 2;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    parse_command(args.into_iter()).map(|command| (command, verbosity))
+}
+
+fn parse_command<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("verify") => parse_run(args)
+            .and_then(only_run_draws)
+            .and_then(no_params)
+            .map(Command::Verify),
+        Some("record") => {
+            let run = text_only(no_params(only_run_draws(parse_run(args)?)?)?)?;
+            if run.day.is_none() {
+                return Err("record needs --day".to_string());
+            }
+            Ok(Command::Record(run))
+        }
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("crosscheck") => parse_crosscheck(args).map(Command::Crosscheck),
+        Some("fetch") => parse_fetch(args).map(Command::Fetch),
+        Some("generate") => parse_generate(args).map(Command::Generate),
+        Some("submit") => {
+            let run = text_only(no_params(only_run_draws(parse_run(args)?)?)?)?;
+            if run.day.is_none() || run.part.is_none() {
+                return Err("submit needs --day and --part".to_string());
+            }
+            if run.source == Source::Example {
+                return Err("there's no point submitting the example's answer".to_string());
+            }
+            Ok(Command::Submit(run))
+        }
+        Some("help") | Some("-h") | Some("--help") | None => Ok(Command::Help),
+        Some(cmd) => Err(format!("unknown command '{cmd}'")),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<RunArgs, String> {
+    let mut run = RunArgs::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => run.day = Some(value(&arg, args.next())?),
+            "-p" | "--part" => {
+                let part = value(&arg, args.next())?;
+                if !(1..=2).contains(&part) {
+                    return Err(format!("part must be 1 or 2, not {part}"));
+                }
+                run.part = Some(part);
+            }
+            "-e" | "--example" => set_source(&mut run.source, Source::Example)?,
+            "--visualize" => run.visualize = true,
+            "--fps" => {
+                let fps = value(&arg, args.next())?;
+                if fps == 0 {
+                    return Err("--fps must be at least 1".to_string());
+                }
+                run.fps = Some(fps);
+            }
+            "--export" => {
+                let dir = args.next().ok_or(format!("{arg} needs a value"))?;
+                run.export.dir = Some(PathBuf::from(dir));
+            }
+            "--frames" => run.export.frames = true,
+            "--scale" => {
+                run.export.scale = match value(&arg, args.next())? {
+                    0 => return Err("--scale must be at least 1".to_string()),
+                    scale => scale,
+                };
+            }
+            "--palette" => {
+                let v = args.next().ok_or(format!("{arg} needs a value"))?;
+                run.export.palette = v.parse()?;
+            }
+            "--image-format" => {
+                let v = args.next().ok_or(format!("{arg} needs a value"))?;
+                run.export.format = v.parse()?;
+            }
+            "--format" => {
+                let v = args.next().ok_or(format!("{arg} needs a value"))?;
+                run.format = v.parse()?;
+            }
+            "--param" => {
+                let v = args.next().ok_or(format!("{arg} needs a value"))?;
+                match v.split_once('=') {
+                    Some((key, value)) if !key.is_empty() => run.params.set(key, value),
+                    _ => return Err(format!("bad value for {arg}: '{v}' (expected KEY=VALUE)")),
+                }
+            }
+            "-" => set_source(&mut run.source, Source::Stdin)?,
+            _ if arg.starts_with('-') => return Err(format!("unexpected argument '{arg}'")),
+            _ => set_source(&mut run.source, Source::Path(PathBuf::from(arg)))?,
+        }
+    }
+    if run.export.dir.is_none() && run.export != Export::default() {
+        return Err("--frames, --scale, --palette and --image-format need --export".to_string());
+    }
+    if run.day.is_none() && !run.params.is_empty() {
+        return Err("--param needs --day".to_string());
+    }
+    if run.day.is_none() && matches!(run.source, Source::Path(_) | Source::Stdin) {
+        return Err("reading an input from a path or stdin needs --day".to_string());
+    }
+    Ok(run)
+}
+
+fn parse_bench<I: Iterator<Item = String>>(mut args: I) -> Result<BenchArgs, String> {
+    let mut bench = BenchArgs::default();
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--runs" => {
+                let v = args.next().ok_or(format!("{arg} needs a value"))?;
+                bench.runs = match v.parse() {
+                    Ok(0) | Err(_) => return Err(format!("bad value for {arg}: '{v}'")),
+                    Ok(runs) => runs,
+                };
+            }
+            "-o" | "--out" => {
+                let v = args.next().ok_or(format!("{arg} needs a value"))?;
+                bench.out = PathBuf::from(v);
+            }
+            _ => rest.push(arg),
+        }
+    }
+    bench.run = text_only(only_run_draws(parse_run(rest.into_iter())?)?)?;
+    Ok(bench)
+}
+
+fn parse_crosscheck<I: Iterator<Item = String>>(mut args: I) -> Result<CrosscheckArgs, String> {
+    let mut crosscheck = CrosscheckArgs::default();
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--timeout" => {
+                crosscheck.timeout = match value(&arg, args.next())? {
+                    0 => return Err("--timeout must be at least 1".to_string()),
+                    secs => Duration::from_secs(secs),
+                };
+            }
+            _ => rest.push(arg),
+        }
+    }
+    crosscheck.run = text_only(no_params(only_run_draws(parse_run(rest.into_iter())?)?)?)?;
+    Ok(crosscheck)
+}
+
+fn parse_fetch<I: Iterator<Item = String>>(mut args: I) -> Result<u8, String> {
+    let mut day = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(value(&arg, args.next())?),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    match day {
+        Some(day @ 1..=25) => Ok(day),
+        Some(day) => Err(format!("there is no day {day}")),
+        None => Err("fetch needs --day".to_string()),
+    }
+}
+
+fn parse_generate<I: Iterator<Item = String>>(mut args: I) -> Result<GenerateArgs, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(value(&arg, args.next())?),
+            "-s" | "--seed" => seed = value(&arg, args.next())?,
+            "--size" => size = Some(value(&arg, args.next())?),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    let day = day.ok_or("generate needs --day")?;
+    Ok(GenerateArgs { day, seed, size })
+}
+
+// drawing would only get in the way of checking, timing or submitting answers
+fn only_run_draws(run: RunArgs) -> Result<RunArgs, String> {
+    if run.visualize || run.fps.is_some() || run.export.dir.is_some() {
+        return Err("--visualize, --fps and --export only work with run".to_string());
+    }
+    Ok(run)
+}
+
+// the answers to puzzles with different parameters aren't the ones to check, keep or submit
+fn no_params(run: RunArgs) -> Result<RunArgs, String> {
+    if !run.params.is_empty() {
+        return Err("--param only works with run and bench".to_string());
+    }
+    Ok(run)
+}
+
+// the other commands have their own output, which isn't a list of answers
+fn text_only(run: RunArgs) -> Result<RunArgs, String> {
+    if run.format != Format::Text {
+        return Err("--format only works with run and verify".to_string());
+    }
+    Ok(run)
+}
+
+fn set_source(current: &mut Source, source: Source) -> Result<(), String> {
+    if *current != Source::Default {
+        return Err("give only one of --example, PATH or -".to_string());
+    }
+    *current = source;
+    Ok(())
+}
+
+fn value<T: FromStr>(flag: &str, v: Option<String>) -> Result<T, String> {
+    let v = v.ok_or(format!("{flag} needs a value"))?;
+    v.parse()
+        .map_err(|_| format!("bad value for {flag}: '{v}'"))
+}
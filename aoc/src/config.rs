@@ -0,0 +1,202 @@
+//! Puzzle parameters, kept in `aoc.toml` under the puzzle root so the answers for each input
+//! don't depend on numbers baked into the solutions.
+//!
+//! The file is a small subset of TOML. A `[dayN]` section sets parameters for every input of day
+//! N, and a `[dayN.NAME]` section for just the input named NAME (`input`, `example`, or a file
+//! stem), taking precedence. Values are integers (with `_` between digits if you like),
+//! `true`/`false`, or strings in double quotes. `--param` overrides all of them.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Params;
+
+use crate::input;
+
+type Section = (u8, Option<String>);
+
+#[derive(Debug, Default)]
+pub struct Config {
+    // `[dayN]` is kept under `(N, None)` and `[dayN.NAME]` under `(N, Some(NAME))`
+    sections: BTreeMap<Section, Params>,
+    overrides: Params,
+}
+
+pub fn path() -> PathBuf {
+    input::root().join("aoc.toml")
+}
+
+impl Config {
+    /// Reads the config file, which doesn't have to exist.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(format!("can't read {}: {err}", path.display())),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut sections = BTreeMap::new();
+        let mut current: Option<Section> = None;
+        for (i, line) in text.lines().enumerate() {
+            let bad = |expected: &str| format!("line {}: expected {expected}", i + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let name = strip_comment(name)
+                    .strip_suffix(']')
+                    .ok_or_else(|| bad("a section name in [brackets]"))?;
+                let section =
+                    section(name).ok_or_else(|| bad("a [dayN] or [dayN.NAME] section"))?;
+                sections.entry(section.clone()).or_insert_with(Params::new);
+                current = Some(section);
+                continue;
+            }
+
+            let (key, raw) = line.split_once('=').ok_or_else(|| bad("<key> = <value>"))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(bad("a parameter name"));
+            }
+            let value =
+                value(raw.trim()).ok_or_else(|| bad("an integer, true, false or a \"string\""))?;
+            let params = match &current {
+                Some(section) => sections.get_mut(section).expect("sections are added first"),
+                None => return Err(bad("a [dayN] section before the first parameter")),
+            };
+            if params.keys().any(|k| k == key) {
+                return Err(format!("line {}: {key} is already set", i + 1));
+            }
+            params.set(key, &value);
+        }
+        Ok(Config {
+            sections,
+            overrides: Params::new(),
+        })
+    }
+
+    /// Uses `overrides` in place of the file's values for every day and input.
+    pub fn with_overrides(self, overrides: Params) -> Self {
+        Config { overrides, ..self }
+    }
+
+    /// The parameters for day `day`'s input named `input`.
+    pub fn params(&self, day: u8, input: &str) -> Params {
+        let mut params = Params::new();
+        for section in [(day, None), (day, Some(input.to_string()))] {
+            if let Some(p) = self.sections.get(&section) {
+                params.extend(p);
+            }
+        }
+        params.extend(&self.overrides);
+        params
+    }
+}
+
+// `dayN` or `dayN.NAME`
+fn section(name: &str) -> Option<Section> {
+    let name = name.trim().strip_prefix("day")?;
+    let (day, input) = match name.split_once('.') {
+        Some((day, input)) if !input.is_empty() => (day, Some(input.to_string())),
+        Some(_) => return None,
+        None => (name, None),
+    };
+    match day.parse() {
+        Ok(day @ 1..=25) => Some((day, input)),
+        _ => None,
+    }
+}
+
+// a value as the solutions will read it: strings unquoted, integers without their underscores
+fn value(s: &str) -> Option<String> {
+    if let Some(quoted) = s.strip_prefix('"') {
+        let (string, rest) = quoted.split_once('"')?;
+        return strip_comment(rest).is_empty().then(|| string.to_string());
+    }
+    let s = strip_comment(s);
+    if s == "true" || s == "false" {
+        return Some(s.to_string());
+    }
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let well_formed = digits
+        .split('_')
+        .all(|group| !group.is_empty() && group.chars().all(|c| c.is_ascii_digit()));
+    well_formed.then(|| s.trim_start_matches('+').replace('_', ""))
+}
+
+// what's left of `s` before a `#` comment
+fn strip_comment(s: &str) -> &str {
+    s.split('#').next().unwrap_or("").trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use aoc_common::Params;
+
+    const CONFIG: &str = "\
+# comment
+[day15]
+row = 2_000_000   # the row to check
+bound = 4000000
+
+[day15.example]
+row = 10
+
+[day22.input]
+face_size = 50
+name = \"a # b\"
+";
+
+    #[test]
+    fn an_inputs_own_section_takes_precedence() {
+        let config = Config::parse(CONFIG).unwrap();
+        let params = |day, input| config.params(day, input);
+        let expected: Params = [("row", "2000000"), ("bound", "4000000")]
+            .into_iter()
+            .collect();
+        assert_eq!(params(15, "input"), expected);
+        let expected: Params = [("row", "10"), ("bound", "4000000")].into_iter().collect();
+        assert_eq!(params(15, "example"), expected);
+        let expected: Params = [("face_size", "50"), ("name", "a # b")]
+            .into_iter()
+            .collect();
+        assert_eq!(params(22, "input"), expected);
+        assert_eq!(params(22, "example"), Params::new());
+    }
+
+    #[test]
+    fn overrides_beat_the_file() {
+        let overrides = [("row", "12")].into_iter().collect();
+        let config = Config::parse(CONFIG).unwrap().with_overrides(overrides);
+        assert_eq!(config.params(15, "example").get("row", 0), Ok(12));
+        assert_eq!(config.params(15, "example").get("bound", 0), Ok(4_000_000));
+    }
+
+    #[test]
+    fn parse_reports_the_bad_line() {
+        let err = |text| Config::parse(text).unwrap_err();
+        assert_eq!(
+            err("row = 10\n"),
+            "line 1: expected a [dayN] section before the first parameter"
+        );
+        assert_eq!(
+            err("[day15]\nrow = ten\n"),
+            "line 2: expected an integer, true, false or a \"string\""
+        );
+        assert_eq!(
+            err("\n[day26]\n"),
+            "line 2: expected a [dayN] or [dayN.NAME] section"
+        );
+        assert_eq!(err("[day1]\na = 1\na = 2\n"), "line 3: a is already set");
+        assert_eq!(
+            err("[day1]\na b = 2\n"),
+            "line 2: expected a parameter name"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! `aoc crosscheck`: runs the Python solutions some days have alongside their Rust ones on the
+//! same input, and compares the answers.
+//!
+//! A Python solution is run from its day's folder as `python3 <script> -`, reads the input from
+//! stdin, and prints a `Part N: <answer>` line for each part it solves.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use aoc_common::Answer;
+
+use crate::cli::RunArgs;
+use crate::days;
+use crate::input::{self, InputError, Source};
+
+#[derive(Debug, PartialEq)]
+pub struct CrosscheckArgs {
+    pub run: RunArgs,
+    /// How long to let each Python run take before giving up on it.
+    pub timeout: Duration,
+}
+
+impl Default for CrosscheckArgs {
+    fn default() -> Self {
+        CrosscheckArgs {
+            run: RunArgs::default(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+// why there are no Python answers to compare with
+enum Failure {
+    TimedOut,
+    Error(String),
+}
+
+/// `$AOC_PYTHON`, or `python3` from the path.
+fn interpreter() -> String {
+    env::var("AOC_PYTHON").unwrap_or_else(|_| "python3".to_string())
+}
+
+// the answers in a Python solution's output, by part
+fn answers(stdout: &str) -> BTreeMap<u8, String> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (part, answer) = line.strip_prefix("Part ")?.split_once(':')?;
+            Some((part.parse().ok()?, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+fn read_all(mut pipe: impl Read + Send + 'static) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = vec![];
+        let _ = pipe.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+// runs the Python solution `script` on `text`, killing it if it takes longer than `timeout`
+fn run_python(
+    script: &Path,
+    text: &str,
+    timeout: Duration,
+) -> Result<BTreeMap<u8, String>, Failure> {
+    let python = interpreter();
+    let (dir, file) = (script.parent(), script.file_name());
+    let mut child = Command::new(&python)
+        .arg(file.expect("scripts are files"))
+        .arg("-")
+        .current_dir(dir.expect("scripts are in a day's folder"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Failure::Error(format!("can't run {python}: {e}")))?;
+
+    // feed it and drain it on other threads, so neither side stalls on a full pipe; a script
+    // that doesn't read all of its input just closes stdin early, which is fine
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let text = text.to_string();
+    thread::spawn(move || stdin.write_all(text.as_bytes()));
+    let stdout = read_all(child.stdout.take().expect("stdout is piped"));
+    let stderr = read_all(child.stderr.take().expect("stderr is piped"));
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() < timeout => thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Failure::TimedOut);
+            }
+            Err(e) => return Err(Failure::Error(format!("can't wait for {python}: {e}"))),
+        }
+    };
+    let stdout = stdout.join().expect("reading stdout doesn't panic");
+    let stderr = stderr.join().expect("reading stderr doesn't panic");
+    if !status.success() {
+        let name = file.expect("scripts are files").to_string_lossy();
+        let mut error = format!("{name} failed ({status})");
+        // the last line of a traceback says what went wrong
+        if let Some(last) = stderr.lines().rfind(|l| !l.trim().is_empty()) {
+            error = format!("{error}: {last}");
+        }
+        return Err(Failure::Error(error));
+    }
+    Ok(answers(&stdout))
+}
+
+// the status and what to show for one part, given each side's answer if it has one
+fn compare(rust: Option<&Answer>, python: Option<&str>) -> (&'static str, String) {
+    match (rust, python) {
+        (Some(rust), Some(python)) if rust.matches(python) => ("same", rust.to_string()),
+        (Some(rust), Some(python)) => ("differ", format!("rust {rust}, python {python}")),
+        (Some(rust), None) => ("rust", format!("{rust} (no python answer)")),
+        (None, Some(python)) => ("python", format!("{python} (not solved in rust)")),
+        (None, None) => ("neither", "solved in neither".to_string()),
+    }
+}
+
+pub fn crosscheck(args: CrosscheckArgs) -> Result<(), String> {
+    let days = match args.run.day {
+        Some(day) if days::python_for(day).is_none() => {
+            return Err(format!("day {day} has no python solution to check against"));
+        }
+        Some(day) => crate::days(Some(day))?,
+        None => crate::days(None)?
+            .into_iter()
+            .filter(|&day| days::python_for(day).is_some())
+            .collect(),
+    };
+    let config = crate::config(&args.run)?;
+    let sources = match &args.run.source {
+        Source::Default => vec![Source::Default, Source::Example],
+        source => vec![source.clone()],
+    };
+
+    println!(
+        "{:>3}  {:>4}  {:<10}  {:<7}  answer",
+        "day", "part", "input", "status"
+    );
+    let (mut failed, mut timed_out) = (0, 0);
+    for day in days {
+        let script = input::root().join(days::python_for(day).expect("only days with python"));
+        for source in &sources {
+            let input = match input::load(day, source) {
+                Ok(input) => input,
+                // not every day has its input downloaded
+                Err(InputError::Missing { .. }) if args.run.source == Source::Default => continue,
+                Err(e) => return Err(e.to_string()),
+            };
+            let python = run_python(&script, &input.text, args.timeout);
+            let parsed = crate::parse(day, &input, &config);
+            for part in crate::parts(args.run.part) {
+                let (status, answer) = match (&parsed, &python) {
+                    (Err(e), _) => ("error", e.clone()),
+                    (_, Err(Failure::Error(e))) => ("error", e.clone()),
+                    (_, Err(Failure::TimedOut)) => {
+                        timed_out += 1;
+                        let secs = args.timeout.as_secs_f64();
+                        ("timeout", format!("python took longer than {secs}s"))
+                    }
+                    (Ok(parsed), Ok(answers)) => match crate::solve(day, part, &**parsed, &input) {
+                        Err(e) => ("error", e),
+                        Ok(Answer::Unsolved(_)) => {
+                            compare(None, answers.get(&part).map(String::as_str))
+                        }
+                        Ok(rust) => compare(Some(&rust), answers.get(&part).map(String::as_str)),
+                    },
+                };
+                if matches!(status, "error" | "differ") {
+                    failed += 1;
+                }
+                println!(
+                    "{day:>3}  {part:>4}  {:<10}  {status:<7}  {answer}",
+                    input.name
+                );
+            }
+        }
+    }
+    if timed_out > 0 {
+        println!("({timed_out} answer(s) not compared in time; --timeout gives python longer)");
+    }
+    if failed > 0 {
+        return Err(format!(
+            "{failed} answer(s) differ between rust and python or couldn't be worked out"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{answers, compare};
+    use aoc_common::Answer;
+
+    #[test]
+    fn answers_are_read_from_part_lines() {
+        let stdout = "loading...\nPart 1: 24000\nPart 2:  45000 \nPart three: 1\n";
+        let answers = answers(stdout);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[&1], "24000");
+        assert_eq!(answers[&2], "45000");
+    }
+
+    #[test]
+    fn mismatches_show_both_answers() {
+        let rust = Answer::from(33usize);
+        assert_eq!(compare(Some(&rust), Some("33")), ("same", "33".to_string()));
+        assert_eq!(
+            compare(Some(&rust), Some("32")),
+            ("differ", "rust 33, python 32".to_string())
+        );
+        assert_eq!(
+            compare(None, Some("301")),
+            ("python", "301 (not solved in rust)".to_string())
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use aoc_common::rng::Rng;
+use aoc_common::{solver, Solver};
+
+pub const DAYS: [u8; 16] = [1, 2, 4, 6, 7, 8, 10, 14, 15, 16, 17, 18, 19, 20, 21, 22];
+
+pub fn solver_for(day: u8) -> Option<Box<dyn Solver>> {
+    let solver = match day {
+        1 => solver::<day1::Puzzle>(),
+        2 => solver::<day2::Puzzle>(),
+        4 => solver::<day4::Puzzle>(),
+        6 => solver::<day6::Puzzle>(),
+        7 => solver::<day7::Puzzle>(),
+        8 => solver::<day8::Puzzle>(),
+        10 => solver::<day10::Puzzle>(),
+        14 => solver::<day14::Puzzle>(),
+        15 => solver::<day15::Puzzle>(),
+        16 => solver::<day16::Puzzle>(),
+        17 => solver::<day17::Puzzle>(),
+        18 => solver::<day18::Puzzle>(),
+        19 => solver::<day19::Puzzle>(),
+        20 => solver::<day20::Puzzle>(),
+        21 => solver::<day21::Puzzle>(),
+        22 => solver::<day22::Puzzle>(),
+        _ => return None,
+    };
+    Some(solver)
+}
+
+/// Where the day's Python solution is under the puzzle root, for the days that have one as well
+/// as a Rust one.
+pub fn python_for(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some("day1/day1.py"),
+        19 => Some("day19/puzzle.py"),
+        21 => Some("day21/puzzle.py"),
+        _ => None,
+    }
+}
+
+/// Makes up a day's inputs: `generate` takes a seed and a size, and `size` is about the size of
+/// a real input.
+pub struct Generator {
+    pub generate: fn(&mut Rng, usize) -> String,
+    pub size: usize,
+}
+
+macro_rules! generator {
+    ($day:ident) => {
+        Generator {
+            generate: $day::generate::generate,
+            size: $day::generate::SIZE,
+        }
+    };
+}
+
+pub fn generator_for(day: u8) -> Option<Generator> {
+    let generator = match day {
+        1 => generator!(day1),
+        2 => generator!(day2),
+        4 => generator!(day4),
+        6 => generator!(day6),
+        7 => generator!(day7),
+        8 => generator!(day8),
+        10 => generator!(day10),
+        14 => generator!(day14),
+        15 => generator!(day15),
+        16 => generator!(day16),
+        17 => generator!(day17),
+        18 => generator!(day18),
+        19 => generator!(day19),
+        20 => generator!(day20),
+        21 => generator!(day21),
+        22 => generator!(day22),
+        _ => return None,
+    };
+    Some(generator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generator_for, solver_for, DAYS};
+    use aoc_common::rng::Rng;
+    use aoc_common::Params;
+
+    #[test]
+    fn generated_inputs_parse_and_solve() {
+        for day in DAYS {
+            let generator = generator_for(day).expect("every day in DAYS has a generator");
+            let solver = solver_for(day).unwrap();
+            for seed in 0..3 {
+                // small enough that even the slow days solve quickly
+                let size = (generator.size / 10).max(2);
+                let input = (generator.generate)(&mut Rng::new(seed), size);
+                let parsed = solver
+                    .parse(&input, &Params::new())
+                    .unwrap_or_else(|e| panic!("seed {seed}: {e}\n{input}"));
+                for part in [1, 2] {
+                    if let Err(e) = parsed.solve(part) {
+                        panic!("seed {seed} part {part}: {e}\n{input}");
+                    }
+                }
+            }
+        }
+    }
+}
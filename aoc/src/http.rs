@@ -0,0 +1,258 @@
+//! A minimal HTTP client. std can speak plain HTTP over a `TcpStream`, which is all a local
+//! stand-in server needs. It has no TLS, so `https` URLs, the real site included, are fetched by
+//! running the `curl` program, which has to be installed for `fetch` and `submit` to reach it.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::process::{self, Stdio};
+
+const USER_AGENT: &str = "aoc-runner (rust, std only)";
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub fn get(url: &str, session: &str) -> Result<Response, String> {
+    request("GET", url, session, None)
+}
+
+/// Posts `form` as `application/x-www-form-urlencoded`.
+pub fn post_form(url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+    let body: Vec<String> = form
+        .iter()
+        .map(|(k, v)| format!("{}={}", url_encode(k), url_encode(v)))
+        .collect();
+    request("POST", url, session, Some(&body.join("&")))
+}
+
+fn request(method: &str, url: &str, session: &str, body: Option<&str>) -> Result<Response, String> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        plain(method, rest, session, body).map_err(|e| format!("{method} {url}: {e}"))
+    } else if url.starts_with("https://") {
+        curl(method, url, session, body)
+    } else {
+        Err(format!(
+            "can't fetch {url}: only http and https are supported"
+        ))
+    }
+}
+
+fn plain(method: &str, url: &str, session: &str, body: Option<&str>) -> Result<Response, String> {
+    let (host, path) = match url.find('/') {
+        Some(i) => (&url[..i], &url[i..]),
+        None => (url, "/"),
+    };
+    let addr = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:80")
+    };
+    let mut stream = TcpStream::connect(&addr).map_err(|e| e.to_string())?;
+
+    // HTTP/1.0 so the reply isn't chunked and ends when the connection closes
+    let mut request = format!(
+        "{method} {path} HTTP/1.0\r\nHost: {host}\r\nUser-Agent: {USER_AGENT}\r\n\
+         Cookie: session={session}\r\n"
+    );
+    if let Some(body) = body {
+        request += "Content-Type: application/x-www-form-urlencoded\r\n";
+        request += &format!("Content-Length: {}\r\n", body.len());
+    }
+    request += "\r\n";
+    request += body.unwrap_or_default();
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let mut reply = vec![];
+    stream.read_to_end(&mut reply).map_err(|e| e.to_string())?;
+    let reply = String::from_utf8_lossy(&reply);
+    let (head, body) = reply
+        .split_once("\r\n\r\n")
+        .ok_or("reply has no end of headers")?;
+    let status = head
+        .split(' ')
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("bad status line {:?}", head.lines().next()))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+fn curl(method: &str, url: &str, session: &str, body: Option<&str>) -> Result<Response, String> {
+    let mut cmd = process::Command::new("curl");
+    cmd.args(["--silent", "--show-error", "--request", method])
+        .args(["--user-agent", USER_AGENT])
+        // the cookie is read from stdin, so it never shows up in the arguments other users can
+        // see in ps or /proc
+        .args(["--config", "-"])
+        // the status goes on a line of its own after the body
+        .args(["--write-out", "\n%{http_code}"]);
+    if let Some(body) = body {
+        cmd.args(["--data", body]);
+    }
+    let mut child = cmd
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => {
+                format!("curl not found: {url} is https, which needs curl installed")
+            }
+            _ => format!("can't run curl for {url}: {e}"),
+        })?;
+    // curl reads all of its config before it starts, so this can't fill up a pipe it's blocked on
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin
+        .write_all(cookie_config(session).as_bytes())
+        .map_err(|e| format!("can't pass the session to curl: {e}"))?;
+    drop(stdin);
+    let out = child
+        .wait_with_output()
+        .map_err(|e| format!("can't run curl for {url}: {e}"))?;
+    if !out.status.success() {
+        let err = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("{method} {url}: {}", err.trim()));
+    }
+    let out = String::from_utf8_lossy(&out.stdout);
+    let (body, status) = out.rsplit_once('\n').ok_or("no status from curl")?;
+    let status = status.parse().map_err(|_| "no status from curl")?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+// a curl config file line sending the session cookie, quoted as curl reads it
+fn cookie_config(session: &str) -> String {
+    let quoted = session.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("cookie = \"session={quoted}\"\n")
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{cookie_config, curl, get, post_form, url_encode};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::process::Command;
+    use std::thread::{self, JoinHandle};
+
+    /// A request as the stand-in server saw it.
+    #[derive(Debug)]
+    pub struct Seen {
+        pub request_line: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Serves `replies` (status, body) to one connection each, in order, then stops. Returns the
+    /// base URL and a handle yielding the requests it saw.
+    pub fn serve(replies: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Seen>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut seen = vec![];
+            for (status, body) in replies {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let (mut cookie, mut length) = (None, 0);
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(c) = line.strip_prefix("Cookie: ") {
+                        cookie = Some(c.to_string());
+                    }
+                    if let Some(l) = line.strip_prefix("Content-Length: ") {
+                        length = l.parse().unwrap();
+                    }
+                }
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                seen.push(Seen {
+                    request_line: request_line.trim_end().to_string(),
+                    cookie,
+                    body: String::from_utf8(request_body).unwrap(),
+                });
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.0 {status} X\r\nContent-Type: text/plain\r\n\r\n{body}"
+                )
+                .unwrap();
+            }
+            seen
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn get_over_plain_http() {
+        let (url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let response = get(&format!("{url}/day/1/input"), "abc").unwrap();
+        assert_eq!(
+            (response.status, response.body.as_str()),
+            (200, "1000\n2000\n")
+        );
+
+        let seen = server.join().unwrap();
+        assert_eq!(seen[0].request_line, "GET /day/1/input HTTP/1.0");
+        assert_eq!(seen[0].cookie.as_deref(), Some("session=abc"));
+        assert_eq!(seen[0].body, "");
+    }
+
+    #[test]
+    fn post_sends_an_encoded_form() {
+        let (url, server) = serve(vec![(200, "ok")]);
+        let form = [("level", "1"), ("answer", "a b&c")];
+        post_form(&format!("{url}/day/1/answer"), "abc", &form).unwrap();
+        let seen = server.join().unwrap();
+        assert_eq!(seen[0].request_line, "POST /day/1/answer HTTP/1.0");
+        assert_eq!(seen[0].body, "level=1&answer=a+b%26c");
+    }
+
+    #[test]
+    fn curl_gets_the_session_from_its_config() {
+        assert_eq!(cookie_config("abc"), "cookie = \"session=abc\"\n");
+        assert_eq!(cookie_config("a\"b\\"), "cookie = \"session=a\\\"b\\\\\"\n");
+        if Command::new("curl").arg("--version").output().is_err() {
+            eprintln!("curl isn't installed, so it can't be run against the stand-in server");
+            return;
+        }
+
+        // curl speaks plain http too, so the stand-in server can check what it sent
+        let (url, server) = serve(vec![(200, "1000\n")]);
+        let response = curl("GET", &format!("{url}/day/1/input"), "abc", None).unwrap();
+        assert_eq!((response.status, response.body.as_str()), (200, "1000\n"));
+        let seen = server.join().unwrap();
+        assert_eq!(seen[0].cookie.as_deref(), Some("session=abc"));
+    }
+
+    #[test]
+    fn url_encoding_keeps_unreserved_characters() {
+        assert_eq!(url_encode("Az09-_.~/="), "Az09-_.~%2F%3D");
+    }
+}
@@ -0,0 +1,111 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where to read a day's puzzle input from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Source {
+    /// `dayN/input.txt` under the puzzle root
+    #[default]
+    Default,
+    /// `dayN/test.input.txt` under the puzzle root
+    Example,
+    Path(PathBuf),
+    Stdin,
+}
+
+/// Puzzle input text, with a short name saying which input it is.
+#[derive(Debug)]
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u8, path: PathBuf },
+    Io { path: PathBuf, err: io::Error },
+    Stdin(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f,
+                "no input for day {day}: {} doesn't exist (download it, or pass a path or - for stdin)",
+                path.display()
+            ),
+            InputError::Io { path, err } => write!(f, "can't read {}: {err}", path.display()),
+            InputError::Stdin(err) => write!(f, "can't read stdin: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The directory holding the `dayN` folders: `$AOC_ROOT` if set, otherwise the workspace this
+/// binary was built from.
+pub fn root() -> PathBuf {
+    match env::var_os("AOC_ROOT") {
+        Some(root) => PathBuf::from(root),
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("the runner lives inside the workspace")
+            .to_path_buf(),
+    }
+}
+
+pub fn default_path(day: u8) -> PathBuf {
+    root().join(format!("day{day}")).join("input.txt")
+}
+
+pub fn example_path(day: u8) -> PathBuf {
+    root().join(format!("day{day}")).join("test.input.txt")
+}
+
+impl Source {
+    /// The short name the input read from here goes by.
+    pub fn name(&self) -> String {
+        match self {
+            Source::Default => "input".to_string(),
+            Source::Example => "example".to_string(),
+            Source::Path(path) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            Source::Stdin => "stdin".to_string(),
+        }
+    }
+}
+
+pub fn load(day: u8, source: &Source) -> Result<Input, InputError> {
+    match source {
+        Source::Default => read_file(&source.name(), default_path(day), day),
+        Source::Example => read_file(&source.name(), example_path(day), day),
+        Source::Path(path) => read_file(&source.name(), path.clone(), day),
+        Source::Stdin => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(InputError::Stdin)?;
+            Ok(Input {
+                name: source.name(),
+                text,
+            })
+        }
+    }
+}
+
+fn read_file(name: &str, path: PathBuf, day: u8) -> Result<Input, InputError> {
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(Input {
+            name: name.to_string(),
+            text,
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Err(InputError::Missing { day, path }),
+        Err(err) => Err(InputError::Io { path, err }),
+    }
+}
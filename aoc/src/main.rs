@@ -0,0 +1,413 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+mod answers;
+mod bench;
+mod cli;
+mod config;
+mod crosscheck;
+mod days;
+mod http;
+mod input;
+mod json;
+mod report;
+mod site;
+mod submit;
+
+use answers::Answers;
+use aoc_common::rng::Rng;
+use aoc_common::{image, log, render, Answer, Parsed};
+use cli::{Command, GenerateArgs, RunArgs};
+use config::Config;
+use input::{Input, InputError, Source};
+use report::{Format, Report};
+use site::Fetched;
+use submit::Outcome;
+
+fn days(day: Option<u8>) -> Result<Vec<u8>, String> {
+    match day {
+        Some(day) if days::DAYS.contains(&day) => Ok(vec![day]),
+        Some(day) => Err(format!("no solution for day {day}")),
+        None => Ok(days::DAYS.to_vec()),
+    }
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+fn config(args: &RunArgs) -> Result<Config, String> {
+    Ok(Config::load(&config::path())?.with_overrides(args.params.clone()))
+}
+
+fn parse(day: u8, input: &Input, config: &Config) -> Result<Box<dyn Parsed>, String> {
+    let solver = days::solver_for(day).expect("every day in DAYS has a solver");
+    let params = config.params(day, &input.name);
+    solver
+        .parse(&input.text, &params)
+        .map_err(|e| format!("{e} (input: {})", input.name))
+}
+
+// a part that panics is reported like one with no answer, so a JSON stream still gets a record
+// for it and for every day after it
+fn solve(day: u8, part: u8, parsed: &dyn Parsed, input: &Input) -> Result<Answer, String> {
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| parsed.solve(part)));
+    let error = match solved {
+        Ok(Ok(answer)) => return Ok(answer),
+        Ok(Err(e)) => e.to_string(),
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().copied();
+            let message = message
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("no message");
+            format!("panicked: {message}")
+        }
+    };
+    Err(format!(
+        "day {day} part {part}: {error} (input: {})",
+        input.name
+    ))
+}
+
+fn print_answer(part: u8, answer: &Answer) {
+    match answer {
+        // letters are shown over the picture they were read from
+        Answer::Bitmap(bitmap) if bitmap.text().is_some() => {
+            println!("Part {part}: {answer}\n{}", bitmap.drawing());
+        }
+        // and pictures that couldn't be read start on their own line
+        Answer::Bitmap(bitmap) => println!("Part {part}:\n{}", bitmap.drawing()),
+        _ => println!("Part {part}: {answer}"),
+    }
+}
+
+// a JSON report with `error` for each part asked for, when there's no input to solve them on
+fn print_errors(day: u8, args: &RunArgs, input: &str, error: &str, parse_time: Option<Duration>) {
+    for part in parts(args.part) {
+        let report = Report {
+            day,
+            part,
+            input,
+            status: "error",
+            answer: Err(error),
+            expected: None,
+            parse_time,
+            solve_time: None,
+        };
+        println!("{}", report.to_json());
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    render::init(args.visualize, args.fps);
+    image::init(args.export.clone());
+    let config = config(&args)?;
+    // as JSON, a day whose input can't be read or parsed is reported like the others and the
+    // rest still run; as text, it stops there
+    let mut failed = 0;
+    for day in days(args.day)? {
+        let input = match input::load(day, &args.source) {
+            Ok(input) => input,
+            Err(e) if args.format == Format::Json => {
+                print_errors(day, &args, &args.source.name(), &e.to_string(), None);
+                failed += parts(args.part).len();
+                continue;
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        let start = Instant::now();
+        let parsed = match parse(day, &input, &config) {
+            Ok(parsed) => parsed,
+            Err(e) if args.format == Format::Json => {
+                print_errors(day, &args, &input.name, &e, Some(start.elapsed()));
+                failed += parts(args.part).len();
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let parse_time = start.elapsed();
+        if args.format == Format::Text {
+            println!("Day {day} ({})", input.name);
+        }
+        for part in parts(args.part) {
+            let start = Instant::now();
+            let solved = solve(day, part, &*parsed, &input);
+            let solve_time = start.elapsed();
+            match args.format {
+                Format::Text => print_answer(part, &solved?),
+                Format::Json => {
+                    let status = match solved {
+                        Ok(Answer::Unsolved(_)) => "unsolved",
+                        Ok(_) => "solved",
+                        Err(_) => {
+                            failed += 1;
+                            "error"
+                        }
+                    };
+                    let report = Report {
+                        day,
+                        part,
+                        input: &input.name,
+                        status,
+                        answer: solved.as_ref().map_err(|e| e.as_str()),
+                        expected: None,
+                        parse_time: Some(parse_time),
+                        solve_time: Some(solve_time),
+                    };
+                    println!("{}", report.to_json());
+                }
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{failed} part(s) couldn't be solved"));
+    }
+    Ok(())
+}
+
+fn verify(args: RunArgs) -> Result<(), String> {
+    let answers = Answers::load(&answers::path())?;
+    let config = config(&args)?;
+    let sources = match &args.source {
+        Source::Default => vec![Source::Default, Source::Example],
+        source => vec![source.clone()],
+    };
+
+    if args.format == Format::Text {
+        println!(
+            "{:>3}  {:>4}  {:<10}  {:<7}  answer",
+            "day", "part", "input", "status"
+        );
+    }
+    let mut failed = 0;
+    for day in days(args.day)? {
+        for source in &sources {
+            let input = match input::load(day, source) {
+                Ok(input) => input,
+                // not every day has its input downloaded
+                Err(InputError::Missing { .. }) if args.source == Source::Default => continue,
+                Err(e) => return Err(e.to_string()),
+            };
+            let start = Instant::now();
+            let parsed = parse(day, &input, &config);
+            let parse_time = start.elapsed();
+            for part in parts(args.part) {
+                let expected = answers.get(day, part, &input.name);
+                let solved = parsed.as_ref().map_err(String::clone).and_then(|parsed| {
+                    let start = Instant::now();
+                    let answer = solve(day, part, &**parsed, &input)?;
+                    Ok((answer, start.elapsed()))
+                });
+                let status = match (&solved, expected) {
+                    (Err(_), _) => "error",
+                    (Ok((answer, _)), Some(expected)) if answer.matches(expected) => "pass",
+                    (Ok(_), Some(_)) => "fail",
+                    (Ok(_), None) => "missing",
+                };
+                if matches!(status, "error" | "fail") {
+                    failed += 1;
+                }
+                if args.format == Format::Json {
+                    let report = Report {
+                        day,
+                        part,
+                        input: &input.name,
+                        status,
+                        answer: solved
+                            .as_ref()
+                            .map(|(answer, _)| answer)
+                            .map_err(|e| e.as_str()),
+                        expected,
+                        parse_time: Some(parse_time),
+                        solve_time: solved.as_ref().ok().map(|&(_, time)| time),
+                    };
+                    println!("{}", report.to_json());
+                    continue;
+                }
+                let shown = match (&solved, expected) {
+                    (Err(e), _) => e.to_string(),
+                    (Ok((answer, _)), Some(expected)) if status == "fail" => {
+                        let shown = answers::escape(&answer.to_string());
+                        format!("{shown}, expected {}", answers::escape(expected))
+                    }
+                    (Ok((answer, _)), _) => answers::escape(&answer.to_string()),
+                };
+                println!(
+                    "{day:>3}  {part:>4}  {:<10}  {status:<7}  {shown}",
+                    input.name
+                );
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{failed} answer(s) don't match answers.txt"));
+    }
+    Ok(())
+}
+
+fn record(args: RunArgs) -> Result<(), String> {
+    let path = answers::path();
+    let mut answers = Answers::load(&path)?;
+    let config = config(&args)?;
+    for day in days(args.day)? {
+        let input = input::load(day, &args.source).map_err(|e| e.to_string())?;
+        if input.name.contains(char::is_whitespace) {
+            return Err(format!("can't record answers for '{}'", input.name));
+        }
+        let parsed = parse(day, &input, &config)?;
+        println!("Day {day} ({})", input.name);
+        for part in parts(args.part) {
+            let answer = solve(day, part, &*parsed, &input)?;
+            print_answer(part, &answer);
+            if let Answer::Unsolved(_) = answer {
+                continue;
+            }
+            if let Some(old) = answers.record(day, part, &input.name, &answer.to_string()) {
+                if !answer.matches(&old) {
+                    println!("(replaces {})", answers::escape(&old));
+                }
+            }
+        }
+    }
+    answers.save(&path)
+}
+
+fn fetch(day: u8) -> Result<(), String> {
+    let dest = input::default_path(day);
+    if dest.exists() {
+        println!("day {day}: already have {}", dest.display());
+        return Ok(());
+    }
+    let (text, fetched) = site::input(&site::base_url(), &site::cache_dir(), day, site::session)?;
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {e}", dir.display()))?;
+    }
+    fs::write(&dest, text).map_err(|e| format!("can't write {}: {e}", dest.display()))?;
+    match fetched {
+        Fetched::Cached => println!("day {day}: copied from the cache to {}", dest.display()),
+        Fetched::Downloaded => println!("day {day}: downloaded to {}", dest.display()),
+    }
+    Ok(())
+}
+
+fn generate(args: GenerateArgs) -> Result<(), String> {
+    days(Some(args.day))?;
+    let generator = days::generator_for(args.day).expect("every day in DAYS has a generator");
+    let size = args.size.unwrap_or(generator.size);
+    print!("{}", (generator.generate)(&mut Rng::new(args.seed), size));
+    Ok(())
+}
+
+fn submit(args: RunArgs) -> Result<(), String> {
+    let (day, part) = match (args.day, args.part) {
+        (Some(day), Some(part)) => (day, part),
+        _ => unreachable!("the command line checks submit has a day and part"),
+    };
+    days(Some(day))?;
+    let input = input::load(day, &args.source).map_err(|e| e.to_string())?;
+    let parsed = parse(day, &input, &config(&args)?)?;
+    let answer = match solve(day, part, &*parsed, &input)? {
+        Answer::Unsolved(file) => {
+            return Err(format!(
+                "day {day} part {part} isn't solved in rust, see {file}"
+            ));
+        }
+        Answer::Bitmap(bitmap) if bitmap.text().is_none() => {
+            let drawing = bitmap.drawing();
+            return Err(format!("can't read the letters in the answer:\n{drawing}"));
+        }
+        answer => answer.to_string(),
+    };
+    println!(
+        "Day {day} part {part} ({}): submitting {answer}",
+        input.name
+    );
+
+    let history = submit::history_path();
+    let outcome = submit::submit(
+        &site::base_url(),
+        &history,
+        day,
+        part,
+        &answer,
+        site::session,
+    )?;
+    println!("{outcome}");
+    if outcome == Outcome::Right {
+        let path = answers::path();
+        let mut answers = Answers::load(&path)?;
+        answers.record(day, part, &input.name, &answer);
+        answers.save(&path)?;
+    }
+    Ok(())
+}
+
+fn execute(command: Command) -> Result<(), String> {
+    match command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Record(args) => record(args),
+        Command::Bench(args) => bench::bench(args),
+        Command::Crosscheck(args) => crosscheck::crosscheck(args),
+        Command::Fetch(day) => fetch(day),
+        Command::Generate(args) => generate(args),
+        Command::Submit(args) => submit(args),
+        Command::Help => {
+            print!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result = match cli::parse(std::env::args().skip(1)) {
+        Ok((command, verbosity)) => log::init(verbosity).and_then(|()| execute(command)),
+        Err(e) => Err(format!("{e}\n\n{}", cli::USAGE)),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solve;
+    use crate::input::Input;
+    use aoc_common::{Answer, NoAnswer, Parsed};
+
+    struct Panics;
+
+    impl Parsed for Panics {
+        fn solve(&self, part: u8) -> Result<Answer, NoAnswer> {
+            match part {
+                1 => panic!("part {part} fell over"),
+                _ => Err(NoAnswer("nowhere to look".to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn panics_are_errors_like_missing_answers() {
+        let input = Input {
+            name: "example".to_string(),
+            text: String::new(),
+        };
+        assert_eq!(
+            solve(15, 1, &Panics, &input),
+            Err("day 15 part 1: panicked: part 1 fell over (input: example)".to_string())
+        );
+        assert_eq!(
+            solve(15, 2, &Panics, &input),
+            Err("day 15 part 2: nowhere to look (input: example)".to_string())
+        );
+    }
+}
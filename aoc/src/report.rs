@@ -0,0 +1,141 @@
+//! `--format json`: what `run` and `verify` found for each part, as a JSON object per line, so
+//! scripts don't have to pick the answers out of the text.
+//!
+//! Every object has the same fields: `day`, `part`, `input`, `status`, `answer` (a number, a
+//! string, or null when there isn't one), `drawing` (the picture a drawn answer was read from),
+//! `expected` (the answer in answers.txt, for verify), `error`, and `parse_ns` and `solve_ns`
+//! for how long parsing the input and solving the part took, null when it didn't get that far.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_common::Answer;
+
+use crate::json;
+
+/// How `run` and `verify` print what they find.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown output format '{s}' (expected text or json)"
+            )),
+        }
+    }
+}
+
+/// One part of one day, solved on one input.
+pub struct Report<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub input: &'a str,
+    pub status: &'a str,
+    /// The answer, or why there isn't one.
+    pub answer: Result<&'a Answer, &'a str>,
+    pub expected: Option<&'a str>,
+    /// Missing when the input couldn't be read.
+    pub parse_time: Option<Duration>,
+    /// Missing when the input didn't parse.
+    pub solve_time: Option<Duration>,
+}
+
+fn or_null(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "null".to_string())
+}
+
+impl Report<'_> {
+    pub fn to_json(&self) -> String {
+        let (answer, drawing) = match self.answer {
+            Ok(Answer::Int(n)) => (Some(n.to_string()), None),
+            Ok(Answer::Text(s)) => (Some(json::string(s)), None),
+            Ok(Answer::Bitmap(bitmap)) => (
+                bitmap.text().map(json::string),
+                Some(json::string(&bitmap.drawing())),
+            ),
+            Ok(Answer::Unsolved(_)) | Err(_) => (None, None),
+        };
+        json::object(&[
+            ("day", self.day.to_string()),
+            ("part", self.part.to_string()),
+            ("input", json::string(self.input)),
+            ("status", json::string(self.status)),
+            ("answer", or_null(answer)),
+            ("drawing", or_null(drawing)),
+            ("expected", or_null(self.expected.map(json::string))),
+            ("error", or_null(self.answer.err().map(json::string))),
+            (
+                "parse_ns",
+                or_null(self.parse_time.map(|t| t.as_nanos().to_string())),
+            ),
+            (
+                "solve_ns",
+                or_null(self.solve_time.map(|t| t.as_nanos().to_string())),
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Report;
+    use aoc_common::{Answer, Bitmap};
+    use std::time::Duration;
+
+    fn report(answer: Result<&Answer, &str>) -> String {
+        Report {
+            day: 10,
+            part: 2,
+            input: "example",
+            status: "pass",
+            answer,
+            expected: Some("HI"),
+            parse_time: Some(Duration::from_micros(5)),
+            solve_time: answer.ok().map(|_| Duration::from_micros(12)),
+        }
+        .to_json()
+    }
+
+    #[test]
+    fn every_report_has_the_same_fields() {
+        let hi: Bitmap = "#..#..###\n#..#...#.\n####...#.\n#..#...#.\n#..#...#.\n#..#..###"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            report(Ok(&Answer::from(hi))),
+            r##"{"day": 10, "part": 2, "input": "example", "status": "pass", "answer": "HI", "drawing": "#..#..###\n#..#...#.\n####...#.\n#..#...#.\n#..#...#.\n#..#..###", "expected": "HI", "error": null, "parse_ns": 5000, "solve_ns": 12000}"##
+        );
+        assert_eq!(
+            report(Ok(&Answer::from(-3isize))),
+            r#"{"day": 10, "part": 2, "input": "example", "status": "pass", "answer": -3, "drawing": null, "expected": "HI", "error": null, "parse_ns": 5000, "solve_ns": 12000}"#
+        );
+        let unreadable = Report {
+            day: 4,
+            part: 1,
+            input: "nope",
+            status: "error",
+            answer: Err("can't read nope.txt"),
+            expected: None,
+            parse_time: None,
+            solve_time: None,
+        };
+        assert_eq!(
+            unreadable.to_json(),
+            r#"{"day": 4, "part": 1, "input": "nope", "status": "error", "answer": null, "drawing": null, "expected": null, "error": "can't read nope.txt", "parse_ns": null, "solve_ns": null}"#
+        );
+        assert_eq!(
+            report(Err("expected a number")),
+            r#"{"day": 10, "part": 2, "input": "example", "status": "pass", "answer": null, "drawing": null, "expected": "HI", "error": "expected a number", "parse_ns": 5000, "solve_ns": null}"#
+        );
+    }
+}
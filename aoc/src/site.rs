@@ -0,0 +1,143 @@
+//! Talking to the puzzle site: where it is, who we are, and downloading inputs.
+//!
+//! The base URL is `$AOC_BASE_URL` (default: this year's event on adventofcode.com), so tests can
+//! point it at a local server. The session token is `$AOC_SESSION`, or the first line of
+//! `.session` under the puzzle root.
+//!
+//! Downloaded inputs are kept in `$AOC_CACHE` (default: `.cache` under the puzzle root), in a
+//! folder for the site they came from, and a cached input is never downloaded again.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{http, input};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+pub fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+pub fn session() -> Result<String, String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+    let path = input::root().join(".session");
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(text.lines().next().unwrap_or_default().trim().to_string()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Err(format!(
+            "no session token: set $AOC_SESSION, or put it in {}",
+            path.display()
+        )),
+        Err(err) => Err(format!("can't read {}: {err}", path.display())),
+    }
+}
+
+pub fn cache_dir() -> PathBuf {
+    match env::var_os("AOC_CACHE") {
+        Some(dir) => PathBuf::from(dir),
+        None => input::root().join(".cache"),
+    }
+}
+
+// the folder in `cache` for inputs from `base_url`, so a stand-in server's inputs never mix with
+// the real site's: `adventofcode.com/2022` for the default, with a port's `:` made a `_`
+fn site_dir(cache: &Path, base_url: &str) -> PathBuf {
+    let site = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest);
+    site.split('/')
+        .filter(|part| !matches!(*part, "" | "." | ".."))
+        .fold(cache.to_path_buf(), |dir, part| {
+            dir.join(part.replace(':', "_"))
+        })
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Day `day`'s input, from the cache in `cache` if it's there, otherwise from the site (asking
+/// `session` for the token only then).
+pub fn input(
+    base_url: &str,
+    cache: &Path,
+    day: u8,
+    session: impl FnOnce() -> Result<String, String>,
+) -> Result<(String, Fetched), String> {
+    let cached = site_dir(cache, base_url).join(format!("day{day}.txt"));
+    if let Ok(text) = fs::read_to_string(&cached) {
+        return Ok((text, Fetched::Cached));
+    }
+
+    let url = format!("{}/day/{day}/input", base_url.trim_end_matches('/'));
+    let response = http::get(&url, &session()?)?;
+    if response.status != 200 {
+        return Err(format!(
+            "can't download day {day}: {url} answered {}: {}",
+            response.status,
+            response.body.trim()
+        ));
+    }
+
+    let dir = cached.parent().expect("inputs are cached in a folder");
+    fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {e}", dir.display()))?;
+    fs::write(&cached, &response.body)
+        .map_err(|e| format!("can't write {}: {e}", cached.display()))?;
+    Ok((response.body, Fetched::Downloaded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input, site_dir, Fetched};
+    use crate::http::tests::serve;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn inputs_are_downloaded_once() {
+        let cache = env::temp_dir().join(format!("aoc-site-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        // the server only answers once, so a second download would fail
+        let (url, server) = serve(vec![(200, "1000\n2000\n")]);
+
+        let session = || Ok("abc".to_string());
+        let first = input(&url, &cache, 1, session).unwrap();
+        assert_eq!(first, ("1000\n2000\n".to_string(), Fetched::Downloaded));
+        let again = input(&url, &cache, 1, || panic!("no token needed")).unwrap();
+        assert_eq!(again, ("1000\n2000\n".to_string(), Fetched::Cached));
+
+        let seen = server.join().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].request_line, "GET /day/1/input HTTP/1.0");
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn failed_downloads_are_not_cached() {
+        let cache = env::temp_dir().join(format!("aoc-site-test-404-{}", std::process::id()));
+        let (url, server) = serve(vec![(404, "Not Found")]);
+        let err = input(&url, &cache, 26, || Ok("abc".to_string())).unwrap_err();
+        assert!(err.contains("answered 404: Not Found"), "{err}");
+        assert!(!site_dir(&cache, &url).join("day26.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn each_site_has_its_own_cache() {
+        let cache = Path::new(".cache");
+        assert_eq!(
+            site_dir(cache, "https://adventofcode.com/2022"),
+            Path::new(".cache/adventofcode.com/2022")
+        );
+        assert_eq!(
+            site_dir(cache, "http://127.0.0.1:8080/"),
+            Path::new(".cache/127.0.0.1_8080")
+        );
+    }
+}
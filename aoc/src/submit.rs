@@ -0,0 +1,263 @@
+//! `aoc submit`: posts an answer to the puzzle site and keeps a history of what it said.
+//!
+//! The history is `.submissions.txt` under the puzzle root, one `<day> <part> <outcome> <answer>`
+//! per line. It's checked before posting, so an answer the site has already turned down is never
+//! sent again, and neither is one on the wrong side of a "too high" or "too low".
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{http, input};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Right,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// with how long the site says to wait, if it said
+    RateLimited(Option<String>),
+    AlreadySolved,
+    Unknown,
+}
+
+impl Outcome {
+    /// Reads the outcome out of the page the site answers a submission with.
+    pub fn parse(page: &str) -> Outcome {
+        if page.contains("That's the right answer") {
+            Outcome::Right
+        } else if page.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if page.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if page.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Outcome::RateLimited(wait)
+        } else if page.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    fn token(&self) -> &'static str {
+        match self {
+            Outcome::Right => "right",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited(_) => "rate-limited",
+            Outcome::AlreadySolved => "already-solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Outcome> {
+        let outcome = match token {
+            "right" => Outcome::Right,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "rate-limited" => Outcome::RateLimited(None),
+            "already-solved" => Outcome::AlreadySolved,
+            "unknown" => Outcome::Unknown,
+            _ => return None,
+        };
+        Some(outcome)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "that's the right answer"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(Some(wait)) => write!(f, "rate limited, wait {wait}"),
+            Outcome::RateLimited(None) => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "that part is already solved"),
+            Outcome::Unknown => write!(f, "couldn't make sense of the reply"),
+        }
+    }
+}
+
+pub fn history_path() -> PathBuf {
+    input::root().join(".submissions.txt")
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<(u8, u8, Outcome, String)>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(err) => return Err(format!("can't read {}: {err}", path.display())),
+        };
+        let mut entries = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let bad = || {
+                let n = i + 1;
+                format!(
+                    "{}: line {n}: expected <day> <part> <outcome> <answer>",
+                    path.display()
+                )
+            };
+            let mut fields = line.splitn(4, ' ');
+            let mut field = || fields.next().ok_or_else(bad);
+            let day = field()?.parse().map_err(|_| bad())?;
+            let part = field()?.parse().map_err(|_| bad())?;
+            let outcome = Outcome::from_token(field()?).ok_or_else(bad)?;
+            entries.push((day, part, outcome, field()?.to_string()));
+        }
+        Ok(History { entries })
+    }
+
+    fn append(&mut self, path: &Path, entry: (u8, u8, Outcome, String)) -> Result<(), String> {
+        let (day, part, outcome, answer) = &entry;
+        let line = format!("{day} {part} {} {answer}\n", outcome.token());
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| format!("can't write {}: {e}", path.display()))?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Why `answer` shouldn't be sent, if the history already rules it out.
+    pub fn refusal(&self, day: u8, part: u8, answer: &str) -> Option<String> {
+        let n: Option<i128> = answer.parse().ok();
+        for (_, _, outcome, old) in self.entries.iter().filter(|e| (e.0, e.1) == (day, part)) {
+            let old_n: Option<i128> = old.parse().ok();
+            let refusal = match outcome {
+                Outcome::Right => format!("day {day} part {part} was already solved with {old}"),
+                Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow if old == answer => {
+                    format!("{answer} was already submitted and was {outcome}")
+                }
+                Outcome::TooHigh if n.zip(old_n).is_some_and(|(n, old)| n > old) => {
+                    format!("{answer} is higher than {old}, which was too high")
+                }
+                Outcome::TooLow if n.zip(old_n).is_some_and(|(n, old)| n < old) => {
+                    format!("{answer} is lower than {old}, which was too low")
+                }
+                _ => continue,
+            };
+            return Some(refusal);
+        }
+        None
+    }
+}
+
+/// Submits `answer` for day `day` part `part`, unless the history at `history` rules it out, and
+/// adds the outcome to the history.
+pub fn submit(
+    base_url: &str,
+    history: &Path,
+    day: u8,
+    part: u8,
+    answer: &str,
+    session: impl FnOnce() -> Result<String, String>,
+) -> Result<Outcome, String> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!(
+            "'{answer}' doesn't look like an answer the site would take"
+        ));
+    }
+    let mut past = History::load(history)?;
+    if let Some(refusal) = past.refusal(day, part, answer) {
+        return Err(format!("not submitting: {refusal}"));
+    }
+
+    let url = format!("{}/day/{day}/answer", base_url.trim_end_matches('/'));
+    let level = part.to_string();
+    let response = http::post_form(&url, &session()?, &[("level", &level), ("answer", answer)])?;
+    if response.status != 200 {
+        return Err(format!(
+            "can't submit day {day} part {part}: {url} answered {}",
+            response.status
+        ));
+    }
+
+    let outcome = Outcome::parse(&response.body);
+    past.append(history, (day, part, outcome.clone(), answer.to_string()))?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{submit, Outcome};
+    use crate::http::tests::serve;
+    use std::env;
+    use std::fs;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data.</p></article>";
+    const RIGHT: &str = "<article><p>That's the right answer! You are one gold star closer \
+        to collecting enough star fruit.</p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 41s left to wait.</p></article>";
+
+    #[test]
+    fn replies_are_understood() {
+        assert_eq!(Outcome::parse(TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(Outcome::parse(RIGHT), Outcome::Right);
+        assert_eq!(
+            Outcome::parse(TOO_SOON),
+            Outcome::RateLimited(Some("41s".to_string()))
+        );
+        assert_eq!(Outcome::parse("<html></html>"), Outcome::Unknown);
+    }
+
+    #[test]
+    fn wrong_answers_are_not_resubmitted() {
+        let history = env::temp_dir().join(format!("aoc-submit-test-{}", std::process::id()));
+        let _ = fs::remove_file(&history);
+        let (url, server) = serve(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let session = || Ok("abc".to_string());
+
+        assert_eq!(
+            submit(&url, &history, 20, 1, "9000", session),
+            Ok(Outcome::TooHigh)
+        );
+        let again = submit(&url, &history, 20, 1, "9000", session).unwrap_err();
+        assert_eq!(
+            again,
+            "not submitting: 9000 was already submitted and was wrong, too high"
+        );
+        let higher = submit(&url, &history, 20, 1, "9001", session).unwrap_err();
+        assert_eq!(
+            higher,
+            "not submitting: 9001 is higher than 9000, which was too high"
+        );
+        assert_eq!(
+            submit(&url, &history, 20, 1, "7153", session),
+            Ok(Outcome::Right)
+        );
+        let solved = submit(&url, &history, 20, 1, "7153", session).unwrap_err();
+        assert_eq!(
+            solved,
+            "not submitting: day 20 part 1 was already solved with 7153"
+        );
+
+        let seen = server.join().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].request_line, "POST /day/20/answer HTTP/1.0");
+        assert_eq!(seen[1].body, "level=1&answer=7153");
+        let text = fs::read_to_string(&history).unwrap();
+        assert_eq!(text, "20 1 too-high 9000\n20 1 right 7153\n");
+        fs::remove_file(&history).unwrap();
+    }
+}
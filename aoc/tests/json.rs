@@ -0,0 +1,76 @@
+//! Runs the `aoc` binary on the examples, to check `--format json` keeps reporting every day when
+//! one of them can't be solved.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn run_examples(root: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "--example", "--format", "json"])
+        .env("AOC_ROOT", root)
+        .output()
+        .unwrap()
+}
+
+// the day, part and status of each record
+fn records(output: &Output) -> Vec<(String, String, String)> {
+    let field = |line: &str, name: &str| {
+        let start = line.find(&format!("\"{name}\": ")).unwrap() + name.len() + 4;
+        let value = &line[start..];
+        value[..value.find(',').unwrap()]
+            .trim_matches('"')
+            .to_string()
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            (
+                field(line, "day"),
+                field(line, "part"),
+                field(line, "status"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn every_day_is_reported_when_one_has_no_answer() {
+    let good = run_examples(&workspace());
+    assert!(good.status.success());
+
+    // a copy of the examples with a day 15 bound that leaves nowhere for the beacon to be
+    let root = std::env::temp_dir().join(format!("aoc-json-{}", std::process::id()));
+    for entry in fs::read_dir(workspace()).unwrap() {
+        let example = entry.unwrap().path().join("test.input.txt");
+        if example.exists() {
+            let day = root.join(example.parent().unwrap().file_name().unwrap());
+            fs::create_dir_all(&day).unwrap();
+            fs::copy(&example, day.join("test.input.txt")).unwrap();
+        }
+    }
+    let config = fs::read_to_string(workspace().join("aoc.toml")).unwrap();
+    let config = config.replace(
+        "[day15.example]\nrow = 10\nbound = 20",
+        "[day15.example]\nrow = 10\nbound = 3",
+    );
+    fs::write(root.join("aoc.toml"), config).unwrap();
+    let bad = run_examples(&root);
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(!bad.status.success());
+    let (good, bad) = (records(&good), records(&bad));
+    let key = |(day, part, _): &(String, String, String)| (day.clone(), part.clone());
+    assert_eq!(
+        good.iter().map(key).collect::<Vec<_>>(),
+        bad.iter().map(key).collect::<Vec<_>>()
+    );
+    assert!(bad.contains(&("15".to_string(), "2".to_string(), "error".to_string())));
+}
@@ -0,0 +1,229 @@
+//! Answers in whichever form a puzzle asks for: a number, some text, or letters drawn in pixels.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Grid, ParseError, Unsolved};
+
+/// A part's answer, as the runner prints, checks and submits it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    Bitmap(Bitmap),
+    /// Only solved in another language, in the file named.
+    Unsolved(&'static str),
+}
+
+impl Answer {
+    /// Whether this is the answer written down as `expected`. A bitmap matches either the
+    /// letters read off it or its drawing.
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Bitmap(bitmap) if bitmap.drawing() == expected => true,
+            answer => answer.to_string() == expected,
+        }
+    }
+}
+
+/// Numbers and text as they are, bitmaps as the letters read off them (or their drawing, if
+/// they can't be read).
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => f.write_str(s),
+            Answer::Bitmap(bitmap) => match bitmap.text() {
+                Some(text) => f.write_str(text),
+                None => f.write_str(&bitmap.drawing()),
+            },
+            Answer::Unsolved(file) => write!(f, "{}", Unsolved(file)),
+        }
+    }
+}
+
+macro_rules! int_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+int_answer!(u8, u32, u64, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<Bitmap> for Answer {
+    fn from(bitmap: Bitmap) -> Self {
+        Answer::Bitmap(bitmap)
+    }
+}
+
+impl From<Unsolved> for Answer {
+    fn from(unsolved: Unsolved) -> Self {
+        Answer::Unsolved(unsolved.0)
+    }
+}
+
+/// Lit and unlit pixels that spell out an answer, like the letters on day 10's CRT, along with
+/// the letters themselves when they're in the font the puzzles use.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bitmap {
+    pixels: Grid<bool>,
+    text: Option<String>,
+}
+
+// the puzzles' letters are 4 pixels wide and 6 high, with a column between each; not every
+// letter has turned up in a puzzle yet, so the font is missing a few
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+const FONT: [(char, [&str; LETTER_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+impl Bitmap {
+    pub fn new(pixels: Grid<bool>) -> Self {
+        let text = read(&pixels);
+        Bitmap { pixels, text }
+    }
+
+    pub fn pixels(&self) -> &Grid<bool> {
+        &self.pixels
+    }
+
+    /// The letters the pixels spell, if every one of them is in the font.
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// The pixels a row per line, `#` for lit and `.` for unlit.
+    pub fn drawing(&self) -> String {
+        let rows: Vec<String> = self
+            .pixels
+            .rows()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect();
+        rows.join("\n")
+    }
+}
+
+/// Reads a drawing of `#` and `.` pixels.
+impl FromStr for Bitmap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        Grid::parse(s, cell, "# or .").map(Bitmap::new)
+    }
+}
+
+// the letters in `pixels`, which must be a row of them with nothing else lit
+fn read(pixels: &Grid<bool>) -> Option<String> {
+    let cell = LETTER_WIDTH + 1;
+    // the last letter doesn't need the column after it
+    let width = pixels.width();
+    if pixels.height() != LETTER_HEIGHT || width == 0 || (width + 1) % cell > 1 {
+        return None;
+    }
+    (0..width.div_ceil(cell))
+        .map(|n| {
+            let left = n * cell;
+            let gap = left + LETTER_WIDTH;
+            if (0..LETTER_HEIGHT).any(|row| pixels.get((row, gap)) == Some(&true)) {
+                return None;
+            }
+            let (letter, _) = FONT.iter().find(|(_, glyph)| {
+                glyph.iter().enumerate().all(|(row, line)| {
+                    line.chars()
+                        .enumerate()
+                        .all(|(col, c)| pixels[(row, left + col)] == (c == '#'))
+                })
+            })?;
+            Some(*letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answer, Bitmap};
+    use crate::Unsolved;
+
+    const HI: &str = "\
+#..#..###.
+#..#...#..
+####...#..
+#..#...#..
+#..#...#..
+#..#..###.";
+
+    #[test]
+    fn letters_are_read_off_bitmaps() {
+        let bitmap: Bitmap = HI.parse().unwrap();
+        assert_eq!(bitmap.text(), Some("HI"));
+        assert_eq!(bitmap.drawing(), HI);
+        // without the column after the last letter
+        let trimmed: Vec<&str> = HI.lines().map(|line| &line[..9]).collect();
+        let bitmap: Bitmap = trimmed.join("\n").parse().unwrap();
+        assert_eq!(bitmap.text(), Some("HI"));
+
+        let smudged = HI.replacen('.', "#", 1);
+        let bitmap: Bitmap = smudged.parse().unwrap();
+        assert_eq!(bitmap.text(), None);
+        assert_eq!(Answer::from(bitmap).to_string(), smudged);
+    }
+
+    #[test]
+    fn answers_match_what_was_written_down() {
+        assert!(Answer::from(24000usize).matches("24000"));
+        assert!(Answer::from(-3isize).matches("-3"));
+        assert!(!Answer::from(24000usize).matches("24001"));
+        assert!(Answer::from("2=-1=0").matches("2=-1=0"));
+
+        let hi = Answer::from(HI.parse::<Bitmap>().unwrap());
+        assert!(hi.matches("HI"));
+        assert!(hi.matches(HI));
+        assert!(!hi.matches("HL"));
+
+        let unsolved = Answer::from(Unsolved("day21/puzzle.py"));
+        assert_eq!(
+            unsolved.to_string(),
+            "not solved in rust, see day21/puzzle.py"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Sets of small numbers packed into the bits of one integer, for states that have to be copied
+//! and hashed a lot (which valves are still closed, which keys have been picked up). Pair with an
+//! [`Interner`](crate::intern::Interner) to number the things being collected.
+
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, Not, Shl};
+
+/// An unsigned integer type to keep a [`BitSet`]'s bits in.
+pub trait Word:
+    Copy
+    + fmt::Debug
+    + Default
+    + Eq
+    + Hash
+    + Ord
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+    + Shl<usize, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const BITS: u32;
+
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const BITS: u32 = <$t>::BITS;
+
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+word!(u64, u128);
+
+/// A set of the numbers `0..W::BITS`.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BitSet<W = u64>(W);
+
+impl<W: Word> BitSet<W> {
+    /// The largest set that fits, `0..W::BITS`.
+    pub const CAPACITY: usize = W::BITS as usize;
+
+    pub fn new() -> Self {
+        BitSet(W::ZERO)
+    }
+
+    fn bit(i: usize) -> W {
+        assert!(
+            i < Self::CAPACITY,
+            "{i} doesn't fit in a set of 0..{}",
+            Self::CAPACITY
+        );
+        W::ONE << i
+    }
+
+    /// Adds `i`, returning whether it was new.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let new = !self.contains(i);
+        self.0 = self.0 | Self::bit(i);
+        new
+    }
+
+    /// Takes out `i`, returning whether it was there.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let was = self.contains(i);
+        self.0 = self.0 & !Self::bit(i);
+        was
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.0 & Self::bit(i) != W::ZERO
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == W::ZERO
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        BitSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        BitSet(self.0 & other.0)
+    }
+
+    /// The numbers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        BitSet(self.0 & !other.0)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// The numbers in the set, lowest first.
+    pub fn iter(&self) -> Iter<W> {
+        Iter(self.0)
+    }
+}
+
+impl<W: Word> fmt::Debug for BitSet<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<W: Word> FromIterator<usize> for BitSet<W> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+impl<W: Word> IntoIterator for BitSet<W> {
+    type Item = usize;
+    type IntoIter = Iter<W>;
+
+    fn into_iter(self) -> Iter<W> {
+        self.iter()
+    }
+}
+
+/// The numbers in a [`BitSet`], lowest first.
+#[derive(Clone, Debug)]
+pub struct Iter<W>(W);
+
+impl<W: Word> Iterator for Iter<W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == W::ZERO {
+            return None;
+        }
+        let i = self.0.trailing_zeros() as usize;
+        self.0 = self.0 & !(W::ONE << i);
+        Some(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitSet;
+
+    #[test]
+    fn inserts_and_removes() {
+        let mut set = BitSet::<u64>::new();
+        assert!(set.insert(3));
+        assert!(set.insert(63));
+        assert!(!set.insert(3));
+        assert!(set.contains(3) && set.contains(63) && !set.contains(4));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [63]);
+        assert_eq!(format!("{set:?}"), "{63}");
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: BitSet<u128> = [1, 5, 100].into_iter().collect();
+        let b: BitSet<u128> = [5, 7, 127].into_iter().collect();
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), [1, 5, 7, 100, 127]);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [5]);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [1, 100]);
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!(BitSet::<u128>::new().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "64 doesn't fit")]
+    fn too_big_to_fit() {
+        BitSet::<u64>::new().insert(64);
+    }
+}
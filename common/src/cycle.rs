@@ -0,0 +1,128 @@
+//! Finding where a simulation starts repeating itself, to skip ahead to step counts far too big
+//! to run.
+//!
+//! The simulation is stepped until a key - everything about its state that decides what happens
+//! next - comes round again. From then on it goes round the same loop forever, so anything that
+//! grows by the same amount each time round (a height, a count) can be worked out for any number
+//! of steps from the one trip round the loop that was seen.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+/// Where a simulation started repeating, and what the metric was after each step up to then.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle<M> {
+    /// how many steps before the loop starts
+    pub start: usize,
+    /// how many steps it takes to go round the loop once
+    pub period: usize,
+    // the metric after 0, 1, ... `start + period` steps
+    metrics: Vec<M>,
+}
+
+/// Steps `state` until its `key` repeats, keeping track of `metric` along the way.
+pub fn detect<S, K, M>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> M,
+) -> Cycle<M>
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::from([(key(&state), 0)]);
+    let mut metrics = vec![metric(&state)];
+    loop {
+        step(&mut state);
+        metrics.push(metric(&state));
+        let steps = metrics.len() - 1;
+        if let Some(start) = seen.insert(key(&state), steps) {
+            return Cycle {
+                start,
+                period: steps - start,
+                metrics,
+            };
+        }
+    }
+}
+
+impl<M> Cycle<M>
+where
+    M: Copy + Add<Output = M> + Sub<Output = M> + Mul<Output = M> + TryFrom<usize>,
+{
+    /// The metric after `steps` steps, assuming it goes up by the same amount every time round
+    /// the loop.
+    pub fn extrapolate(&self, steps: usize) -> M {
+        if steps < self.metrics.len() {
+            return self.metrics[steps];
+        }
+        let (laps, into_lap) = (
+            (steps - self.start) / self.period,
+            (steps - self.start) % self.period,
+        );
+        let per_lap = self.metrics[self.start + self.period] - self.metrics[self.start];
+        let laps = M::try_from(laps).unwrap_or_else(|_| panic!("{laps} laps is too many to count"));
+        self.metrics[self.start + into_lap] + laps * per_lap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect;
+
+    // a pseudo-random walk round 0..97 that falls into a loop after a while
+    fn next(x: &mut u64) {
+        *x = (*x * *x + 11) % 97;
+    }
+
+    #[test]
+    fn finds_the_loop() {
+        let cycle = detect(3, next, |&x| x, |&x| x);
+        let mut x = 3;
+        for _ in 0..cycle.start {
+            next(&mut x);
+        }
+        let first = x;
+        for lap in 1..=cycle.period {
+            next(&mut x);
+            assert_eq!(x == first, lap == cycle.period);
+        }
+    }
+
+    #[test]
+    fn extrapolates_like_brute_force() {
+        // the running total of the walk, with the position in the walk as the state
+        let cycle = detect(
+            (3, 0),
+            |(x, total)| {
+                next(x);
+                *total += *x;
+            },
+            |&(x, _)| x,
+            |&(_, total)| total,
+        );
+
+        let (mut x, mut total) = (3, 0);
+        for steps in 0..500 {
+            assert_eq!(cycle.extrapolate(steps), total, "after {steps} steps");
+            next(&mut x);
+            total += x;
+        }
+    }
+
+    #[test]
+    fn extrapolates_a_long_way() {
+        // goes up by 1, 2, 3, 1, 2, 3, ... after a run-up of 10
+        let total = |n: u64| match n {
+            0..10 => n,
+            _ => 10 + (n - 10) / 3 * 6 + [0, 1, 3][((n - 10) % 3) as usize],
+        };
+        let key = |&n: &u64| if n < 10 { n } else { 10 + (n - 10) % 3 };
+        let cycle = detect(0, |n| *n += 1, key, |&n| total(n));
+        assert_eq!((cycle.start, cycle.period), (10, 3));
+        for steps in [1_000_000_000_000, 1_000_000_000_001, 1_000_000_000_002] {
+            assert_eq!(cycle.extrapolate(steps as usize), total(steps));
+        }
+    }
+}
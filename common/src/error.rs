@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fmt;
+
+/// The puzzle input didn't look the way the solution expected.
+///
+/// Parsers build one of these from the text they choked on and a description of what should have
+/// been there, then fill in where it was as the error makes its way out: the line (and column,
+/// when the bad text is part of a longer line), and finally the day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    /// 1-based
+    pub line: Option<usize>,
+    /// 1-based, in bytes
+    pub column: Option<usize>,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(found: &str, expected: &str) -> Self {
+        ParseError {
+            day: None,
+            line: None,
+            column: None,
+            found: found.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// `part` of `s` doesn't match `expected`; the column is where `part` starts within `s`.
+    pub fn within(s: &str, part: &str, expected: &str) -> Self {
+        ParseError {
+            column: offset_in(s, part).map(|offset| offset + 1),
+            ..ParseError::new(part, expected)
+        }
+    }
+
+    /// Places the error on line `number` (1-based) of the input.
+    pub fn on_line(self, number: usize) -> Self {
+        ParseError {
+            line: Some(number),
+            ..self
+        }
+    }
+
+    /// Places an error found while parsing `s` on line `number`, where `s` is part of `line`.
+    /// Any column already set relative to `s` is shifted to be relative to `line`.
+    pub fn at(self, number: usize, line: &str, s: &str) -> Self {
+        self.relative_to(line, s).on_line(number)
+    }
+
+    /// Moves an error found while parsing `part` of `s` to be relative to all of `s`: a column
+    /// already set is shifted, and one that isn't is the start of `part`.
+    pub fn relative_to(self, s: &str, part: &str) -> Self {
+        let column = match (offset_in(s, part), self.column) {
+            (Some(offset), Some(column)) => Some(offset + column),
+            (Some(offset), None) => Some(offset + 1),
+            (None, column) => column,
+        };
+        ParseError { column, ..self }
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+// byte offset of `inner` within `outer`, if `inner` is a slice of `outer`
+fn offset_in(outer: &str, inner: &str) -> Option<usize> {
+    let start = (inner.as_ptr() as usize).checked_sub(outer.as_ptr() as usize)?;
+    (start + inner.len() <= outer.len()).then_some(start)
+}
+
+/// The lines of `s`, numbered from 1 to match `ParseError::line`.
+pub fn numbered_lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = [
+            self.day.map(|day| format!("day {day}")),
+            self.line.map(|line| format!("line {line}")),
+            self.column.map(|column| format!("column {column}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        write!(f, "expected {}, found {:?}", self.expected, self.found)
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn display_without_location() {
+        let e = ParseError::new("", "a number");
+        assert_eq!(e.to_string(), "expected a number, found \"\"");
+    }
+
+    #[test]
+    fn display_with_location() {
+        let line = "Sensor at x=2, y=1o: closest beacon is at x=-2, y=15";
+        let e = ParseError::within(line, &line[17..19], "an integer")
+            .on_line(3)
+            .in_day(15);
+        assert_eq!(
+            e.to_string(),
+            "day 15, line 3, column 18: expected an integer, found \"1o\""
+        );
+    }
+
+    #[test]
+    fn column_is_shifted_into_the_line() {
+        let line = "move 1 from 2 to x";
+        let words = &line[12..];
+        let e = ParseError::within(words, &words[5..], "a stack").at(4, line, words);
+        assert_eq!((e.line, e.column), (Some(4), Some(18)));
+    }
+
+    #[test]
+    fn column_defaults_to_the_start_of_the_part() {
+        let line = "$ cd";
+        let e = ParseError::new(&line[2..], "a directory").at(7, line, &line[2..]);
+        assert_eq!((e.line, e.column), (Some(7), Some(3)));
+    }
+}
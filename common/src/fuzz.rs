@@ -0,0 +1,178 @@
+//! Randomised checks for the parsers, for the days' `tests/fuzz.rs`, which call [`check`].
+//!
+//! It starts from a corpus of inputs: the day's example, its real input if that's been
+//! downloaded, and a few small ones made up by its generator. Each has to parse, and read back
+//! the same line by line, so nothing gets lost or misread along the way. Then it messes them up
+//! at random (cuts, stray characters, huge numbers, shuffled lines, ...) and checks parsing them
+//! only ever returns an error, never panics. The mutations start from a seed, `$FUZZ_SEED` if
+//! it's set (0 otherwise), and a failure names the seed that replays it; `$FUZZ_CASES` sets how
+//! many mutated inputs to try.
+
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::rng::Rng;
+use crate::{ParseError, Solution};
+
+// numbers at the edges of what the integer types hold
+const EXTREMES: [&str; 7] = [
+    "0",
+    "-1",
+    "4294967296",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "18446744073709551615",
+    "99999999999999999999",
+];
+
+// bits of text that parsers tend to trip over
+const NASTY: [&str; 11] = [
+    "-", "+", " ", "  ", "\n", "\n\n", ",", ": ", " -> ", "$ ", "é",
+];
+
+fn env_or<T: std::str::FromStr>(var: &str, default: T) -> T {
+    env::var(var)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Fuzzes day `S`, whose folder is `dir` and whose inputs `generate` makes up, and which reads
+/// each line of its input as a `T`.
+pub fn check<S, T>(dir: &str, generate: fn(&mut Rng, usize) -> String)
+where
+    S: Solution,
+    T: FromStr<Err = ParseError> + Display,
+{
+    let inputs = corpus(dir, generate);
+    for (name, input) in &inputs {
+        if let Err(e) = S::parse(input) {
+            panic!("{name} didn't parse: {e}");
+        }
+        round_trip(input, |line| line.parse::<T>().map(|t| t.to_string()));
+    }
+    let texts: Vec<String> = inputs.into_iter().map(|(_, input)| input).collect();
+    mutations(&texts, S::parse);
+}
+
+// the inputs to check a day's parser on, with what to call them
+fn corpus(dir: &str, generate: fn(&mut Rng, usize) -> String) -> Vec<(String, String)> {
+    let dir = Path::new(dir);
+    let mut inputs = vec![];
+    for file in ["test.input.txt", "input.txt"] {
+        if let Ok(text) = fs::read_to_string(dir.join(file)) {
+            inputs.push((file.to_string(), text));
+        }
+    }
+    // always the same ones, so a failure replays from its seed
+    inputs.extend((0..3).map(|seed| {
+        let name = format!("the input generated from seed {seed}");
+        (name, generate(&mut Rng::new(seed), 20))
+    }));
+    inputs
+}
+
+// checks that `reformat` (parse a line, then format what it read) gives back every line of
+// `input` unchanged
+fn round_trip<E: Display>(input: &str, reformat: impl Fn(&str) -> Result<String, E>) {
+    for (i, line) in input.lines().enumerate() {
+        match reformat(line) {
+            Ok(again) => assert_eq!(again, line, "line {} came back different", i + 1),
+            Err(e) => panic!("line {} didn't parse: {e}\n{line}", i + 1),
+        }
+    }
+}
+
+/// `input` with a few random changes.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1..=4) {
+        let at = rng.range(0..=chars.len());
+        match rng.range(0..8) {
+            // cut out a bit
+            0 if at < chars.len() => {
+                let end = rng.range(at + 1..=chars.len().min(at + 8));
+                chars.drain(at..end);
+            }
+            // stop early
+            1 => chars.truncate(at),
+            // repeat a bit
+            2 if at < chars.len() => {
+                let end = rng.range(at + 1..=chars.len().min(at + 8));
+                let copy: Vec<char> = chars[at..end].to_vec();
+                chars.splice(at..at, copy);
+            }
+            // change one character to another that's somewhere in the input
+            3 if at < chars.len() => chars[at] = *rng.pick(&chars),
+            // put in something nasty
+            4 | 5 => {
+                chars.splice(at..at, rng.pick(&NASTY).chars());
+            }
+            // swap the number around a digit for an extreme one
+            6 if chars.get(at).is_some_and(char::is_ascii_digit) => {
+                let start = (0..at)
+                    .rev()
+                    .find(|&i| !chars[i].is_ascii_digit())
+                    .map_or(0, |i| i + 1);
+                let end = (at..chars.len())
+                    .find(|&i| !chars[i].is_ascii_digit())
+                    .unwrap_or(chars.len());
+                chars.splice(start..end, rng.pick(&EXTREMES).chars());
+            }
+            // swap two lines
+            _ => {
+                let text: String = chars.iter().collect();
+                let mut lines: Vec<&str> = text.lines().collect();
+                if !lines.is_empty() {
+                    let (a, b) = (rng.range(0..lines.len()), rng.range(0..lines.len()));
+                    lines.swap(a, b);
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// parses mutated copies of `inputs` with `parse`, and fails with the mutated input and the seed
+// that makes it if parsing panics rather than returning an error
+fn mutations<T>(inputs: &[String], parse: impl Fn(&str) -> Result<T, ParseError>) {
+    let seed = env_or("FUZZ_SEED", 0);
+    let cases = env_or("FUZZ_CASES", 500);
+    for case in 0..cases {
+        let case_seed = seed + case;
+        let mut rng = Rng::new(case_seed);
+        let input = rng.pick(inputs);
+        let mutated = mutate(&mut rng, input);
+        if panic::catch_unwind(AssertUnwindSafe(|| parse(&mutated))).is_err() {
+            panic!(
+                "parsing panicked on this input (replay it with FUZZ_SEED={case_seed} \
+                 FUZZ_CASES=1):\n{mutated}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mutate, round_trip};
+    use crate::rng::Rng;
+
+    #[test]
+    fn mutations_are_reproducible() {
+        let input = "1,2 -> 3,4\n5,6 -> 7,8\n";
+        let mutated = |seed| mutate(&mut Rng::new(seed), input);
+        assert_eq!(mutated(3), mutated(3));
+        assert!((0..10).any(|seed| mutated(seed) != input));
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 came back different")]
+    fn round_trip_spots_a_changed_line() {
+        round_trip("1\n02\n", |line| line.parse::<u8>().map(|n| n.to_string()));
+    }
+}
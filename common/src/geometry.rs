@@ -0,0 +1,278 @@
+//! Points in 2d and 3d space, for the puzzles that happen off a character map.
+//!
+//! Coordinates are signed, so points can double as the vectors between them: subtracting two
+//! points gives the step from one to the other, and adding a step to a point moves it.
+
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed integer type to use as a coordinate.
+pub trait Coord:
+    Copy
+    + fmt::Debug
+    + fmt::Display
+    + Eq
+    + Hash
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+coord!(i8, i16, i32, i64, i128, isize);
+
+/// How far apart two points are.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Metric {
+    /// the sum of the distances along each axis: steps when moving orthogonally
+    Manhattan,
+    /// the largest distance along any axis: steps when diagonal moves are allowed too
+    Chebyshev,
+    /// the straight line distance, squared to stay in integers
+    EuclideanSquared,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point2<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// steps to the neighbours along a single axis and along more than one, for each number of
+// axes, in the order of their coordinates; -1, 0 and 1 are turned into a `Coord` as they're used
+const ORTHOGONAL_2: [[i8; 2]; 4] = [[-1, 0], [0, -1], [0, 1], [1, 0]];
+const DIAGONAL_2: [[i8; 2]; 4] = [[-1, -1], [-1, 1], [1, -1], [1, 1]];
+const ORTHOGONAL_3: [[i8; 3]; 6] = [
+    [-1, 0, 0],
+    [0, -1, 0],
+    [0, 0, -1],
+    [0, 0, 1],
+    [0, 1, 0],
+    [1, 0, 0],
+];
+const DIAGONAL_3: [[i8; 3]; 20] = [
+    [-1, -1, -1],
+    [-1, -1, 0],
+    [-1, -1, 1],
+    [-1, 0, -1],
+    [-1, 0, 1],
+    [-1, 1, -1],
+    [-1, 1, 0],
+    [-1, 1, 1],
+    [0, -1, -1],
+    [0, -1, 1],
+    [0, 1, -1],
+    [0, 1, 1],
+    [1, -1, -1],
+    [1, -1, 0],
+    [1, -1, 1],
+    [1, 0, -1],
+    [1, 0, 1],
+    [1, 1, -1],
+    [1, 1, 0],
+    [1, 1, 1],
+];
+
+fn unit<T: Coord>(step: i8) -> T {
+    match step {
+        -1 => -T::ONE,
+        0 => T::ZERO,
+        _ => T::ONE,
+    }
+}
+
+// everything that doesn't care how many axes there are, written once for both points
+macro_rules! point {
+    ($point:ident, $n:literal, $orthogonal:ident, $diagonal:ident, $($axis:ident),+) => {
+        impl<T: Coord> $point<T> {
+            pub fn new($($axis: T),+) -> Self {
+                $point { $($axis),+ }
+            }
+
+            pub fn distance(self, other: Self, metric: Metric) -> T {
+                let d = other - self;
+                match metric {
+                    Metric::Manhattan => T::ZERO $(+ d.$axis.abs())+,
+                    Metric::Chebyshev => T::ZERO $(.max(d.$axis.abs()))+,
+                    Metric::EuclideanSquared => T::ZERO $(+ d.$axis * d.$axis)+,
+                }
+            }
+
+            pub fn manhattan(self, other: Self) -> T {
+                self.distance(other, Metric::Manhattan)
+            }
+
+            pub fn chebyshev(self, other: Self) -> T {
+                self.distance(other, Metric::Chebyshev)
+            }
+
+            pub fn euclidean_squared(self, other: Self) -> T {
+                self.distance(other, Metric::EuclideanSquared)
+            }
+
+            fn step([$($axis),+]: [i8; $n]) -> Self {
+                $point { $($axis: unit($axis)),+ }
+            }
+
+            /// The neighbours one step along a single axis.
+            pub fn orthogonal_neighbours(self) -> impl Iterator<Item = Self> {
+                $orthogonal.into_iter().map(move |step| self + Self::step(step))
+            }
+
+            /// The neighbours one step along more than one axis at once.
+            pub fn diagonal_neighbours(self) -> impl Iterator<Item = Self> {
+                $diagonal.into_iter().map(move |step| self + Self::step(step))
+            }
+
+            /// Every neighbour, orthogonal and diagonal.
+            pub fn neighbours(self) -> impl Iterator<Item = Self> {
+                self.orthogonal_neighbours().chain(self.diagonal_neighbours())
+            }
+        }
+
+        impl<T: Coord> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $point { $($axis: self.$axis + other.$axis),+ }
+            }
+        }
+
+        impl<T: Coord> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $point { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl<T: Coord> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($axis: -self.$axis),+ }
+            }
+        }
+
+        /// Scales a vector.
+        impl<T: Coord> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, k: T) -> Self {
+                $point { $($axis: self.$axis * k),+ }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+point!(Point2, 2, ORTHOGONAL_2, DIAGONAL_2, x, y);
+point!(Point3, 3, ORTHOGONAL_3, DIAGONAL_3, x, y, z);
+
+/// `x,y`, the way the puzzles write them.
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// `x,y,z`, the way the puzzles write them.
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Metric, Point2, Point3};
+
+    #[test]
+    fn points_are_vectors() {
+        let p = Point2::new(3, -4);
+        let q = Point2::new(-1, 2);
+        assert_eq!(p + q, Point2::new(2, -2));
+        assert_eq!(p - q, Point2::new(4, -6));
+        assert_eq!(-p * 2, Point2::new(-6, 8));
+        let mut r = p;
+        r += q;
+        r -= p;
+        assert_eq!(r, q);
+    }
+
+    #[test]
+    fn distances() {
+        let p = Point2::new(0, 0);
+        let q = Point2::new(3, -4);
+        assert_eq!(p.manhattan(q), 7);
+        assert_eq!(p.chebyshev(q), 4);
+        assert_eq!(p.euclidean_squared(q), 25);
+        let a = Point3::new(1, 1, 1);
+        let b = Point3::new(2, -1, 3);
+        assert_eq!(a.distance(b, Metric::Manhattan), 5);
+        assert_eq!(a.distance(b, Metric::Chebyshev), 2);
+        assert_eq!(a.distance(b, Metric::EuclideanSquared), 9);
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point2::new(5i64, 5);
+        let orthogonal: Vec<_> = p.orthogonal_neighbours().collect();
+        assert_eq!(orthogonal.len(), 4);
+        assert!(orthogonal.iter().all(|n| n.manhattan(p) == 1));
+        assert_eq!(p.diagonal_neighbours().count(), 4);
+        assert_eq!(p.neighbours().count(), 8);
+
+        let c = Point3::new(0, 0, 0);
+        assert_eq!(c.orthogonal_neighbours().count(), 6);
+        assert_eq!(c.diagonal_neighbours().count(), 20);
+        assert!(c.diagonal_neighbours().all(|n| n.manhattan(c) > 1));
+        assert!(c.neighbours().all(|n| n.chebyshev(c) == 1));
+    }
+
+    #[test]
+    fn display_like_the_input() {
+        assert_eq!(Point2::new(498, 4).to_string(), "498,4");
+        assert_eq!(Point3::new(2, -2, 5).to_string(), "2,-2,5");
+    }
+}
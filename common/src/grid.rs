@@ -0,0 +1,284 @@
+//! A dense 2d grid for the puzzles that come as a character map.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{numbered_lines, ParseError};
+
+/// A `(row, col)` position in a grid.
+pub type Pos = (usize, usize);
+
+/// `(row, col)` steps to the four orthogonal neighbours: up, left, right, down.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// `(row, col)` steps to all eight neighbours, in reading order.
+pub const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A dense rectangle of cells, stored row by row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Adds a row of `fill` after the last one.
+    pub fn push_row(&mut self, fill: T) {
+        self.cells.extend(std::iter::repeat_n(fill, self.width));
+        self.height += 1;
+    }
+
+    /// Parses a character map whose lines may be shorter than the longest one, filling out the
+    /// short ones with `fill`.
+    pub fn parse_ragged(
+        s: &str,
+        fill: T,
+        cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let rows = parse_rows(s, cell, expected)?;
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let cells = rows
+            .into_iter()
+            .flat_map(|mut row| {
+                row.resize(width, fill.clone());
+                row
+            })
+            .collect();
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a rectangular character map, one row per line, turning each character into a cell
+    /// with `cell`. `expected` describes a good character, for the error when `cell` rejects one.
+    pub fn parse(
+        s: &str,
+        cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let rows = parse_rows(s, cell, expected)?;
+        let width = rows[0].len();
+        if let Some((n, line)) = numbered_lines(s).find(|(_, line)| line.chars().count() != width) {
+            return Err(ParseError::new(line, &format!("a row of {width} cells")).on_line(n));
+        }
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The position one `(row, col)` step from `pos`, if it's on the grid.
+    pub fn step(&self, (row, col): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The orthogonal neighbours of `pos` that are on the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// All the neighbours of `pos`, diagonals included, that are on the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_AROUND
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The positions from `pos` (not included) to the edge of the grid, stepping by `dir`.
+    pub fn ray(&self, pos: Pos, dir: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, dir), move |&p| self.step(p, dir))
+    }
+
+    /// Drops the first `n` rows.
+    pub fn remove_rows(&mut self, n: usize) {
+        let n = n.min(self.height);
+        self.cells.drain(..n * self.width);
+        self.height -= n;
+    }
+}
+
+fn parse_rows<T>(
+    s: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+    expected: &str,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let rows = numbered_lines(s)
+        .map(|(n, line)| {
+            line.char_indices()
+                .map(|(j, c)| {
+                    cell(c).ok_or_else(|| {
+                        let bad = &line[j..j + c.len_utf8()];
+                        ParseError::within(line, bad, expected).on_line(n)
+                    })
+                })
+                .collect::<Result<Vec<T>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if rows.first().is_none_or(Vec::is_empty) {
+        return Err(ParseError::new("", "a grid"));
+    }
+    Ok(rows)
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is off the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is off the grid"))
+    }
+}
+
+/// Draws the grid a row per line, each cell with its own `Display`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    fn digits(s: &str) -> Grid<u32> {
+        Grid::parse(s, |c| c.to_digit(10), "a digit").unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let grid = digits("123\n456");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_bad_cell() {
+        let err = Grid::parse("123\n4x6", |c| c.to_digit(10), "a digit").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected a digit, found \"x\""
+        );
+        let err = Grid::parse("123\n45", |c| c.to_digit(10), "a digit").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected a row of 3 cells, found \"45\""
+        );
+    }
+
+    #[test]
+    fn ragged_maps_are_filled_out() {
+        let grid = Grid::parse_ragged("  #\n#", ' ', Some, "anything").unwrap();
+        assert_eq!(grid.to_string(), "  #\n#  ");
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = digits("123\n456\n789");
+        let corner: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn rays_run_to_the_edge() {
+        let grid = digits("123\n456\n789");
+        let left: Vec<u32> = grid.ray((1, 2), (0, -1)).map(|p| grid[p]).collect();
+        assert_eq!(left, [5, 4]);
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn rows_can_be_added_and_removed() {
+        let mut grid = digits("12");
+        grid.push_row(0);
+        grid.push_row(9);
+        grid.remove_rows(1);
+        assert_eq!(grid.to_string(), "00\n99");
+    }
+}
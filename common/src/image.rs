@@ -0,0 +1,508 @@
+//! Saving pictures of a puzzle's state as image files, for write-ups.
+//!
+//! The solutions describe a picture as a grid of cells, each drawn in one of the day's palette
+//! of colours, and the runner's `--export` decides where the files go, how big each cell is and
+//! whether the day's own colours are swapped for others. As with [`render`](crate::render), the
+//! pictures are only built when they'll be saved. Files are PNG by default, or binary PPM, which
+//! nearly anything can read and is trivial to write.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use crate::grid::{Grid, Pos};
+use crate::warn;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// A colour from its `0xrrggbb` hex code.
+    pub const fn hex(code: u32) -> Rgb {
+        Rgb((code >> 16) as u8, (code >> 8) as u8, code as u8)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    /// Parses `rrggbb`, with or without a leading `#`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        match u32::from_str_radix(digits, 16) {
+            Ok(code) if digits.len() == 6 => Ok(Rgb::hex(code)),
+            _ => Err(format!("bad colour '{s}' (use rrggbb hex)")),
+        }
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colours to use in place of a day's own, in the order the day lists them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Palette(pub Vec<Rgb>);
+
+impl Palette {
+    /// The colour for palette entry `i`, falling back to the day's own when this palette is too
+    /// short.
+    pub fn colour(&self, i: usize, own: &[Rgb]) -> Rgb {
+        self.0.get(i).copied().unwrap_or(own[i])
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    /// Parses comma separated colours.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Palette)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Png,
+    Ppm,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Ppm => "ppm",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Format::Png),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(format!("unknown image format '{s}' (use png or ppm)")),
+        }
+    }
+}
+
+/// Where and how to save pictures.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Export {
+    /// the folder to save them in, or `None` to not save any
+    pub dir: Option<PathBuf>,
+    /// whether to save every frame of the animations too, not just the finished pictures
+    pub frames: bool,
+    /// how many pixels wide and high each cell is
+    pub scale: usize,
+    pub palette: Palette,
+    pub format: Format,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Export {
+            dir: None,
+            frames: false,
+            scale: 4,
+            palette: Palette::default(),
+            format: Format::default(),
+        }
+    }
+}
+
+struct Exporter {
+    export: Export,
+    // the next frame number for each animation
+    frames: HashMap<String, usize>,
+}
+
+static EXPORTER: Mutex<Option<Exporter>> = Mutex::new(None);
+
+/// Turns saving on, if `export` has somewhere to save to.
+pub fn init(export: Export) {
+    let exporter = export.dir.is_some().then(|| Exporter {
+        export,
+        frames: HashMap::new(),
+    });
+    *EXPORTER.lock().unwrap() = exporter;
+}
+
+pub fn enabled() -> bool {
+    EXPORTER.lock().unwrap().is_some()
+}
+
+/// Saves the finished picture as `name` (plus the extension) in the export folder.
+pub fn save(name: &str, image: impl FnOnce(&Palette) -> Image) {
+    let mut exporter = EXPORTER.lock().unwrap();
+    if let Some(Exporter { export, .. }) = exporter.as_mut() {
+        write(export, name, image);
+    }
+}
+
+/// Saves the next frame of the animation `name` as `name-00001` and so on, when saving frames.
+pub fn frame(name: &str, image: impl FnOnce(&Palette) -> Image) {
+    let mut exporter = EXPORTER.lock().unwrap();
+    if let Some(Exporter { export, frames }) = exporter.as_mut() {
+        if export.frames {
+            let n = frames.entry(name.to_string()).or_insert(0);
+            *n += 1;
+            write(export, &format!("{name}-{n:05}"), image);
+        }
+    }
+}
+
+// saving is a side show to solving the puzzle, so failing to doesn't stop anything
+fn write(export: &Export, name: &str, image: impl FnOnce(&Palette) -> Image) {
+    let dir = export.dir.as_deref().expect("only set up with a folder");
+    let path = dir.join(name).with_extension(export.format.extension());
+    let image = image(&export.palette).scaled(export.scale);
+    if let Err(e) = image.save(&path, export.format) {
+        warn!("can't save {}: {e}", path.display());
+    }
+}
+
+/// A picture, a row of pixels at a time from the top.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// A picture with a pixel per `(row, col)` cell, coloured with entry `cell(pos)` of the day's
+    /// palette `own`, unless `palette` overrides it.
+    pub fn from_cells(
+        width: usize,
+        height: usize,
+        palette: &Palette,
+        own: &[Rgb],
+        mut cell: impl FnMut(Pos) -> usize,
+    ) -> Self {
+        let colours: Vec<Rgb> = (0..own.len()).map(|i| palette.colour(i, own)).collect();
+        let mut image = Image::new(width, height, Rgb::default());
+        for row in 0..height {
+            for col in 0..width {
+                image.pixels[row * width + col] = colours[cell((row, col))];
+            }
+        }
+        image
+    }
+
+    /// A picture of `grid`, like `from_cells`.
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        palette: &Palette,
+        own: &[Rgb],
+        mut cell: impl FnMut(Pos, &T) -> usize,
+    ) -> Self {
+        Image::from_cells(grid.width(), grid.height(), palette, own, |pos| {
+            cell(pos, &grid[pos])
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<Rgb> {
+        (row < self.height && col < self.width).then(|| self.pixels[row * self.width + col])
+    }
+
+    /// Colours the pixel at `(row, col)`, if it's in the picture.
+    pub fn set(&mut self, (row, col): Pos, colour: Rgb) {
+        if row < self.height && col < self.width {
+            self.pixels[row * self.width + col] = colour;
+        }
+    }
+
+    /// The same picture with every pixel blown up to `scale` by `scale`.
+    pub fn scaled(&self, scale: usize) -> Image {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let wide: Vec<Rgb> = row
+                .iter()
+                .flat_map(|&p| std::iter::repeat_n(p, scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&wide);
+            }
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn save(&self, path: &Path, format: Format) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {e}", dir.display()))?;
+        }
+        let bytes = match format {
+            Format::Png => self.to_png(),
+            Format::Ppm => self.to_ppm(),
+        };
+        fs::write(path, bytes).map_err(|e| e.to_string())
+    }
+
+    /// The picture as a binary (P6) PPM file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for &Rgb(r, g, b) in &self.pixels {
+            out.extend([r, g, b]);
+        }
+        out
+    }
+
+    /// The picture as an 8-bit RGB PNG file.
+    pub fn to_png(&self) -> Vec<u8> {
+        // each row of pixels starts with the filter it uses, which is always none
+        let stride = 1 + 3 * self.width;
+        let mut raw = Vec::with_capacity(stride * self.height);
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0);
+            for &Rgb(r, g, b) in row {
+                raw.extend([r, g, b]);
+            }
+        }
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, then the standard compression, filtering and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut out, b"IHDR", &header);
+        chunk(&mut out, b"IDAT", &zlib(&raw, stride));
+        chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let table: Vec<u32> = (0..256)
+        .map(|n| {
+            (0..8).fold(n, |c, _| {
+                if c & 1 == 1 {
+                    0xedb8_8320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                }
+            })
+        })
+        .collect();
+    !bytes.iter().fold(!0, |crc, &b| {
+        table[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+// writes bits into bytes, lowest bit first, as deflate wants
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    acc: u32,
+    n: u32,
+}
+
+impl Bits {
+    fn put(&mut self, bits: u32, n: u32) {
+        self.acc |= bits << self.n;
+        self.n += n;
+        while self.n >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.n -= 8;
+        }
+    }
+
+    // Huffman codes go in highest bit first
+    fn code(&mut self, code: u32, n: u32) {
+        self.put(code.reverse_bits() >> (32 - n), n);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.n > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+const LENGTH_BASE: [u32; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u32; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+// a literal byte or length code, in deflate's fixed Huffman code
+fn literal(bits: &mut Bits, symbol: u32) {
+    match symbol {
+        0..=143 => bits.code(0x30 + symbol, 8),
+        144..=255 => bits.code(0x190 + symbol - 144, 9),
+        256..=279 => bits.code(symbol - 256, 7),
+        _ => bits.code(0xc0 + symbol - 280, 8),
+    }
+}
+
+// `len` bytes again from `distance` back
+fn copy(bits: &mut Bits, len: u32, distance: u32) {
+    let i = LENGTH_BASE.partition_point(|&base| base <= len) - 1;
+    literal(bits, 257 + i as u32);
+    bits.put(len - LENGTH_BASE[i], LENGTH_EXTRA[i]);
+    let j = DISTANCE_BASE.partition_point(|&base| base <= distance) - 1;
+    bits.code(j as u32, 5);
+    bits.put(distance - DISTANCE_BASE[j], DISTANCE_EXTRA[j]);
+}
+
+// compresses `data` in a single fixed-code deflate block, wrapped for zlib. Puzzle pictures are
+// mostly runs of one colour and rows the same as the one above, so it's enough to only look for
+// repeats of the last pixel and of the last row (`stride` bytes back).
+fn zlib(data: &[u8], stride: usize) -> Vec<u8> {
+    const MAX_LEN: usize = 258;
+    const MAX_DISTANCE: usize = 32768;
+
+    let mut bits = Bits::default();
+    // the last block, using the fixed codes
+    bits.put(1, 1);
+    bits.put(1, 2);
+    let mut i = 0;
+    while i < data.len() {
+        let best = [3, stride]
+            .into_iter()
+            .filter(|&d| d <= i && d <= MAX_DISTANCE)
+            .map(|d| {
+                let len = (0..MAX_LEN.min(data.len() - i))
+                    .take_while(|&k| data[i + k] == data[i + k - d])
+                    .count();
+                (len, d)
+            })
+            .max();
+        match best {
+            Some((len, d)) if len >= 3 => {
+                copy(&mut bits, len as u32, d as u32);
+                i += len;
+            }
+            _ => {
+                literal(&mut bits, data[i] as u32);
+                i += 1;
+            }
+        }
+    }
+    literal(&mut bits, 256);
+
+    // deflate, no preset dictionary, default level
+    let mut out = vec![0x78, 0x01];
+    out.extend(bits.finish());
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adler32, crc32, Image, Palette, Rgb};
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn colours_and_palettes_parse() {
+        assert_eq!("#ff8000".parse(), Ok(Rgb(255, 128, 0)));
+        assert_eq!(Rgb::hex(0x0000ff).to_string(), "#0000ff");
+        assert!("fff".parse::<Rgb>().is_err());
+        let palette: Palette = "000000,#ffffff".parse().unwrap();
+        let own = [Rgb(1, 1, 1), Rgb(2, 2, 2), Rgb(3, 3, 3)];
+        assert_eq!(palette.colour(1, &own), Rgb(255, 255, 255));
+        assert_eq!(palette.colour(2, &own), Rgb(3, 3, 3));
+    }
+
+    #[test]
+    fn cells_are_scaled_up() {
+        let own = [Rgb(0, 0, 0), Rgb(255, 0, 0)];
+        let image = Image::from_cells(2, 1, &Palette::default(), &own, |(_, col)| col);
+        let big = image.scaled(2);
+        assert_eq!((big.width(), big.height()), (4, 2));
+        assert_eq!(big.get((1, 1)), Some(Rgb(0, 0, 0)));
+        assert_eq!(big.get((1, 2)), Some(Rgb(255, 0, 0)));
+        assert_eq!(big.get((2, 0)), None);
+    }
+
+    #[test]
+    fn ppm_has_a_header_then_the_pixels() {
+        let image = Image::new(2, 1, Rgb(1, 2, 3));
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x01\x02\x03\x01\x02\x03");
+    }
+
+    #[test]
+    fn png_is_made_of_checksummed_chunks() {
+        let png = Image::new(3, 2, Rgb(9, 9, 9)).to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let mut rest = &png[8..];
+        let mut kinds = vec![];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (body, crc) = rest[4..].split_at(4 + len);
+            assert_eq!(crc32(body).to_be_bytes(), crc[..4]);
+            kinds.push(String::from_utf8(body[..4].to_vec()).unwrap());
+            rest = &crc[4..];
+        }
+        assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+    }
+}
@@ -0,0 +1,69 @@
+//! Numbering the names in a puzzle (valves, monkeys, ...) so the solution can work with small
+//! indices instead of strings: they're `Copy`, cheap to hash and compare, and index straight
+//! into a `Vec` or a [`BitSet`](crate::bitset::BitSet).
+
+use std::collections::HashMap;
+
+/// Names, each numbered by when it was first seen, from 0 up.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// The index of `name`, numbering it if it's new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&i) = self.indices.get(name) {
+            return i;
+        }
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    /// The index of `name`, if it's been numbered.
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    /// The name numbered `i`.
+    pub fn name(&self, i: usize) -> &str {
+        &self.names[i]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Every name, in index order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interner;
+
+    #[test]
+    fn names_are_numbered_in_order_once() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("AA"), 0);
+        assert_eq!(names.intern("BB"), 1);
+        assert_eq!(names.intern("AA"), 0);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.get("BB"), Some(1));
+        assert_eq!(names.get("CC"), None);
+        assert_eq!(names.name(1), "BB");
+        assert_eq!(names.names().collect::<Vec<_>>(), ["AA", "BB"]);
+    }
+}
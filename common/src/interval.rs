@@ -0,0 +1,220 @@
+//! Sets of integers kept as the inclusive ranges they cover, for puzzles about stretches of a
+//! line (section assignments, the part of a row a sensor can see) that are too long to hold a
+//! point at a time.
+
+use std::ops::RangeInclusive;
+
+use crate::geometry::Coord;
+
+/// A set of integers, stored as sorted ranges that neither overlap nor touch.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet<T = isize> {
+    // (start, end), both included
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Coord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    /// Adds every integer in `range`, merging it with any ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e + T::ONE < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end + T::ONE);
+        if first < last {
+            start = start.min(self.ranges[first].0);
+            end = end.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    /// Takes out every integer in `range`, splitting any range it falls in the middle of.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        let mut kept = vec![];
+        if first < last {
+            let (s, _) = self.ranges[first];
+            if s < start {
+                kept.push((s, start - T::ONE));
+            }
+            let (_, e) = self.ranges[last - 1];
+            if e > end {
+                kept.push((end + T::ONE, e));
+            }
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.ranges() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let start = a[i].0.max(b[j].0);
+            let end = a[i].1.min(b[j].1);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // whichever ends first can't overlap anything further along the other
+            if a[i].1 < b[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |len, &(s, e)| len + (e - s + T::ONE))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, e)| e < x);
+        i < self.ranges.len() && self.ranges[i].0 <= x
+    }
+
+    /// Whether every integer in `range` is in the set.
+    pub fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|&(_, e)| e < *range.start());
+        i < self.ranges.len()
+            && self.ranges[i].0 <= *range.start()
+            && self.ranges[i].1 >= *range.end()
+    }
+
+    /// Whether any integer in `range` is in the set.
+    pub fn overlaps(&self, range: &RangeInclusive<T>) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+        let i = self.ranges.partition_point(|&(_, e)| e < *range.start());
+        i < self.ranges.len() && self.ranges[i].0 <= *range.end()
+    }
+
+    /// The ranges in the set, lowest first.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// The stretches of `within` that aren't in the set, lowest first.
+    pub fn gaps(&self, within: RangeInclusive<T>) -> impl Iterator<Item = RangeInclusive<T>> {
+        IntervalSet::from(within)
+            .difference(self)
+            .ranges
+            .into_iter()
+            .map(|(s, e)| s..=e)
+    }
+}
+
+impl<T: Coord> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Coord> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    fn ranges(set: &IntervalSet) -> Vec<(isize, isize)> {
+        set.ranges().map(|r| (*r.start(), *r.end())).collect()
+    }
+
+    #[test]
+    fn inserts_merge_overlapping_and_touching_ranges() {
+        let mut set: IntervalSet = [1..=3, 10..=12, -5..=-2].into_iter().collect();
+        assert_eq!(ranges(&set), [(-5, -2), (1, 3), (10, 12)]);
+        set.insert(4..=6);
+        assert_eq!(ranges(&set), [(-5, -2), (1, 6), (10, 12)]);
+        set.insert(-1..=11);
+        assert_eq!(ranges(&set), [(-5, 12)]);
+        assert_eq!(set.len(), 18);
+    }
+
+    #[test]
+    fn removes_split_ranges() {
+        let mut set: IntervalSet = [0..=10, 20..=30].into_iter().collect();
+        set.remove(3..=5);
+        assert_eq!(ranges(&set), [(0, 2), (6, 10), (20, 30)]);
+        set.remove(8..=25);
+        assert_eq!(ranges(&set), [(0, 2), (6, 7), (26, 30)]);
+        set.remove(-100..=100);
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 0);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: IntervalSet = [0..=10, 20..=30].into_iter().collect();
+        let b: IntervalSet = [5..=25, 40..=40].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), [(0, 30), (40, 40)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(5, 10), (20, 25)]);
+        assert_eq!(ranges(&a.difference(&b)), [(0, 4), (26, 30)]);
+        assert_eq!(ranges(&b.difference(&a)), [(11, 19), (40, 40)]);
+    }
+
+    #[test]
+    fn membership_and_gaps() {
+        let set: IntervalSet = [0..=10, 20..=30].into_iter().collect();
+        assert!(set.contains(0) && set.contains(25));
+        assert!(!set.contains(-1) && !set.contains(15) && !set.contains(31));
+        assert!(set.contains_range(&(2..=8)));
+        assert!(!set.contains_range(&(8..=22)));
+        assert!(set.overlaps(&(8..=22)));
+        assert!(!set.overlaps(&(11..=19)));
+        assert_eq!(
+            set.gaps(-5..=35).collect::<Vec<_>>(),
+            [-5..=-1, 11..=19, 31..=35]
+        );
+        assert_eq!(set.gaps(2..=8).count(), 0);
+    }
+}
@@ -0,0 +1,28 @@
+//! Shared pieces for the daily puzzle crates and the `aoc` runner.
+
+mod answer;
+pub mod bitset;
+pub mod cycle;
+mod error;
+pub mod fuzz;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod intern;
+pub mod interval;
+pub mod log;
+mod params;
+pub mod render;
+pub mod rng;
+pub mod scan;
+pub mod search;
+mod solution;
+
+pub use answer::{Answer, Bitmap};
+pub use bitset::BitSet;
+pub use error::{numbered_lines, ParseError};
+pub use geometry::{Point2, Point3};
+pub use grid::Grid;
+pub use interval::IntervalSet;
+pub use params::Params;
+pub use solution::{solver, IntoAnswer, NoAnswer, Parsed, Solution, Solver, Unsolved};
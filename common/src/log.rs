@@ -0,0 +1,127 @@
+//! Leveled logging to stderr.
+//!
+//! Only warnings are shown unless the level is raised, by the runner's `-v` (debug) and `-vv`
+//! (trace) flags or by `$AOC_LOG`, so a normal run prints just the answers. The macros check the
+//! level before formatting anything, so events in hot loops cost next to nothing when they're off.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+impl Level {
+    /// The level for `-v` repeated `count` times.
+    pub fn from_verbosity(count: u8) -> Level {
+        match count {
+            0 => Level::Warn,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!(
+                "unknown log level '{s}' (use warn, info, debug or trace)"
+            )),
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Sets the level from the `-v` count, falling back to `$AOC_LOG` when there were none.
+pub fn init(verbosity: u8) -> Result<(), String> {
+    let level = match (verbosity, env::var("AOC_LOG")) {
+        (0, Ok(level)) => level.parse()?,
+        (count, _) => Level::from_verbosity(count),
+    };
+    set_level(level);
+    Ok(())
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, target: &str, args: fmt::Arguments) {
+    eprintln!("[{} {target}] {args}", level.name());
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Level;
+
+    #[test]
+    fn verbosity_raises_the_level() {
+        assert_eq!(Level::from_verbosity(0), Level::Warn);
+        assert_eq!(Level::from_verbosity(1), Level::Debug);
+        assert_eq!(Level::from_verbosity(5), Level::Trace);
+    }
+
+    #[test]
+    fn levels_parse_from_their_names() {
+        assert_eq!("Trace".parse(), Ok(Level::Trace));
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Debug > Level::Info);
+    }
+}
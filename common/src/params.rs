@@ -0,0 +1,82 @@
+//! Puzzle parameters that aren't part of the input text: the row day 15 checks, how many rocks
+//! day 17 drops, ... The runner collects them for each input from `aoc.toml` and `--param`, and
+//! each day reads the ones it knows, keeping the values for the real input for any that aren't
+//! given.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// Parameter values, by name, still as text.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    /// Sets `key`, replacing any earlier value.
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+
+    /// Sets everything in `other`, replacing the values here.
+    pub fn extend(&mut self, other: &Params) {
+        self.0
+            .extend(other.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// The names of the parameters that are set.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value of `key`, or `default` if it isn't set.
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, ParseError> {
+        match self.0.get(key) {
+            None => Ok(default),
+            Some(v) => v
+                .parse()
+                .map_err(|_| ParseError::new(v, &format!("a valid {key}"))),
+        }
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Params(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Params;
+
+    #[test]
+    fn unset_parameters_keep_their_default() {
+        let mut params: Params = [("row", "10")].into_iter().collect();
+        params.extend(&[("row", "12"), ("bound", "20")].into_iter().collect());
+        assert_eq!(params.get("row", 2_000_000), Ok(12));
+        assert_eq!(params.get("bound", 4_000_000), Ok(20));
+        assert_eq!(params.get("minutes", 24), Ok(24));
+        assert_eq!(params.keys().collect::<Vec<_>>(), ["bound", "row"]);
+    }
+
+    #[test]
+    fn bad_values_are_parse_errors() {
+        let err = Params::from_iter([("row", "ten")])
+            .get("row", 0)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "expected a valid row, found \"ten\"");
+    }
+}
@@ -0,0 +1,160 @@
+//! Drawing a simulation's state in the terminal, as a still picture or frame by frame.
+//!
+//! Nothing is drawn unless the runner's `--visualize` (or `--fps`) turned it on, and the
+//! pictures are only built when they'll be shown, so the solutions can call [`frame`] and
+//! [`show`] in their main loops without slowing down a normal run. Pictures go to stderr, so
+//! the answers on stdout stay clean, and are coloured when stderr is a terminal.
+
+use std::env;
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Write as _};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::{Grid, Pos};
+
+static ON: AtomicBool = AtomicBool::new(false);
+// frames a second, or 0 to only show the finished pictures
+static FPS: AtomicU32 = AtomicU32::new(0);
+static COLOUR: AtomicBool = AtomicBool::new(false);
+
+/// Turns drawing on, animating at `fps` frames a second if that's given.
+pub fn init(visualize: bool, fps: Option<u32>) {
+    ON.store(visualize || fps.is_some(), Ordering::Relaxed);
+    FPS.store(fps.unwrap_or(0), Ordering::Relaxed);
+    let colour = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    COLOUR.store(colour, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ON.load(Ordering::Relaxed)
+}
+
+pub fn animating() -> bool {
+    enabled() && FPS.load(Ordering::Relaxed) > 0
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    // the ANSI code that sets the text to this colour
+    fn code(self) -> u8 {
+        match self {
+            Colour::Red => 91,
+            Colour::Green => 92,
+            Colour::Yellow => 93,
+            Colour::Blue => 94,
+            Colour::Magenta => 95,
+            Colour::Cyan => 96,
+            Colour::White => 97,
+            Colour::Grey => 90,
+        }
+    }
+}
+
+/// What to draw for one cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Glyph {
+    pub ch: char,
+    pub colour: Option<Colour>,
+}
+
+impl Glyph {
+    pub const fn plain(ch: char) -> Glyph {
+        Glyph { ch, colour: None }
+    }
+
+    pub const fn coloured(ch: char, colour: Colour) -> Glyph {
+        Glyph {
+            ch,
+            colour: Some(colour),
+        }
+    }
+}
+
+/// A `width` by `height` picture, with the glyph for each `(row, col)` from `cell`.
+pub fn picture(width: usize, height: usize, mut cell: impl FnMut(Pos) -> Glyph) -> String {
+    let colour = COLOUR.load(Ordering::Relaxed);
+    let mut out = String::new();
+    for row in 0..height {
+        // only switch colour where it changes along the row
+        let mut current = None;
+        for col in 0..width {
+            let glyph = cell((row, col));
+            if colour && glyph.colour != current {
+                match glyph.colour {
+                    Some(c) => write!(out, "\x1b[{}m", c.code()).unwrap(),
+                    None => out.push_str("\x1b[0m"),
+                }
+                current = glyph.colour;
+            }
+            out.push(glyph.ch);
+        }
+        if current.is_some() {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// A picture of `grid`, with the glyph for each cell from `cell`.
+pub fn grid<T>(grid: &Grid<T>, mut cell: impl FnMut(Pos, &T) -> Glyph) -> String {
+    picture(grid.width(), grid.height(), |pos| cell(pos, &grid[pos]))
+}
+
+/// One frame of an animation: when animating, clears the terminal, draws the picture under
+/// `title`, and waits until it's time for the next frame. Does nothing otherwise.
+pub fn frame(title: &str, picture: impl FnOnce() -> String) {
+    if !animating() {
+        return;
+    }
+    eprint!("\x1b[H\x1b[2J{title}\n{}", picture());
+    io::stderr().flush().ok();
+    let fps = FPS.load(Ordering::Relaxed);
+    thread::sleep(Duration::from_secs(1) / fps);
+}
+
+/// The finished picture, drawn under `title` whenever drawing is on.
+pub fn show(title: &str, picture: impl FnOnce() -> String) {
+    if enabled() {
+        eprint!("{title}\n{}", picture());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grid, picture, Colour, Glyph, COLOUR};
+    use crate::Grid;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn pictures_are_drawn_row_by_row() {
+        let g = Grid::parse("#.\n.#", Some, "a cell").unwrap();
+        let drawn = grid(&g, |_, &c| Glyph::plain(if c == '#' { 'o' } else { ' ' }));
+        assert_eq!(drawn, "o \n o\n");
+    }
+
+    #[test]
+    fn colours_change_only_where_they_differ() {
+        COLOUR.store(true, Ordering::Relaxed);
+        let drawn = picture(4, 1, |(_, col)| match col {
+            0 | 1 => Glyph::coloured('#', Colour::Red),
+            2 => Glyph::plain('.'),
+            _ => Glyph::coloured('o', Colour::Yellow),
+        });
+        COLOUR.store(false, Ordering::Relaxed);
+        assert_eq!(drawn, "\x1b[91m##\x1b[0m.\x1b[93mo\x1b[0m\n");
+    }
+}
@@ -0,0 +1,117 @@
+//! A small seeded random number generator, for making up puzzle inputs. The same seed gives the
+//! same numbers on every machine and every run, so a generated input can be described by its
+//! seed and size alone.
+
+use std::ops::{Range, RangeInclusive};
+
+/// SplitMix64: one 64-bit word of state, which is plenty for test data.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // a number in 0..n, scaled rather than taken modulo n so it stays close to uniform
+    fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number picked evenly from `range`, which mustn't be empty.
+    pub fn range<T, R: SampleRange<T>>(&mut self, range: R) -> T {
+        range.sample(self)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// One of `items`, which mustn't be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// A range of integers that [`Rng::range`] can pick a `T` from.
+pub trait SampleRange<T> {
+    fn sample(self, rng: &mut Rng) -> T;
+}
+
+// offsets are added with wrapping arithmetic, which lands in the right place for signed types
+// too since the offset is less than the width of the range
+macro_rules! sample_range {
+    ($($t:ty),*) => {
+        $(
+            impl SampleRange<$t> for Range<$t> {
+                fn sample(self, rng: &mut Rng) -> $t {
+                    assert!(self.start < self.end, "can't pick from the empty range {self:?}");
+                    let width = self.end.abs_diff(self.start) as u64;
+                    self.start.wrapping_add(rng.below(width) as $t)
+                }
+            }
+
+            impl SampleRange<$t> for RangeInclusive<$t> {
+                fn sample(self, rng: &mut Rng) -> $t {
+                    let (start, end) = self.into_inner();
+                    assert!(start <= end, "can't pick from the empty range {start}..={end}");
+                    match end.abs_diff(start) as u64 {
+                        u64::MAX => rng.next_u64() as $t,
+                        width => start.wrapping_add(rng.below(width + 1) as $t),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+sample_range!(u8, u32, u64, usize, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn the_same_seed_gives_the_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+    }
+
+    #[test]
+    fn picks_stay_in_range() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let n = rng.range(-3..=3isize);
+            assert!((-3..=3).contains(&n));
+            seen[(n + 3) as usize] = true;
+            assert!((250..300).contains(&rng.range(250..300u32)));
+            assert!((i64::MIN..=i64::MAX).contains(&rng.range(i64::MIN..=i64::MAX)));
+        }
+        assert!(seen.iter().all(|&s| s), "every value turns up");
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+}
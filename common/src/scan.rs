@@ -0,0 +1,156 @@
+//! Reading lines that follow a fixed wording, like
+//! `"Sensor at x={}, y={}: closest beacon is at x={}, y={}"`.
+//!
+//! The text between the `{}`s has to be in the line word for word, and each `{}` captures
+//! everything up to where the next piece of text starts (or the end of the line, for a `{}` at
+//! the end of the template). The captures come back in order, to be parsed into whatever type
+//! they should be.
+
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// What a line's `{}`s captured.
+#[derive(Debug)]
+pub struct Captures<'a> {
+    line: &'a str,
+    holes: std::vec::IntoIter<&'a str>,
+}
+
+impl<'a> Captures<'a> {
+    /// The next capture as it was in the line.
+    pub fn text(&mut self) -> &'a str {
+        self.holes
+            .next()
+            .expect("the template should have a {} for every capture taken")
+    }
+
+    /// The next capture parsed as a `T`, or an error pointing at it when it doesn't parse.
+    pub fn parse<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let hole = self.text();
+        hole.parse()
+            .map_err(|_| ParseError::within(self.line, hole, expected))
+    }
+}
+
+/// Matches `line` against `template`. When the wording doesn't match, the error points at the
+/// first piece of text that's not where it should be.
+pub fn scan<'a>(line: &'a str, template: &str) -> Result<Captures<'a>, ParseError> {
+    let pieces: Vec<&str> = template.split("{}").collect();
+    let mut rest = expect_text(line, line, pieces[0])?;
+
+    let mut holes = vec![];
+    for (i, piece) in pieces.iter().enumerate().skip(1) {
+        let end = match piece.chars().next() {
+            Some(c) => rest.find(c).unwrap_or(rest.len()),
+            None if i + 1 < pieces.len() => panic!("template {template:?} has two {{}}s in a row"),
+            None => rest.len(),
+        };
+        holes.push(&rest[..end]);
+        rest = expect_text(line, &rest[end..], piece)?;
+    }
+    if !rest.is_empty() {
+        return Err(ParseError::within(line, rest, "the end of the line"));
+    }
+
+    Ok(Captures {
+        line,
+        holes: holes.into_iter(),
+    })
+}
+
+/// Matches `line` against each of `templates` in turn, for lines that can be worded more than one
+/// way. When none match, the error is from the template that matched the most of the line.
+pub fn scan_any<'a>(line: &'a str, templates: &[&str]) -> Result<Captures<'a>, ParseError> {
+    let mut furthest: Option<ParseError> = None;
+    for template in templates {
+        match scan(line, template) {
+            Ok(captures) => return Ok(captures),
+            Err(e) => {
+                if furthest.as_ref().is_none_or(|f| e.column > f.column) {
+                    furthest = Some(e);
+                }
+            }
+        }
+    }
+    Err(furthest.expect("there should be at least one template"))
+}
+
+// `rest` (the unmatched end of `line`) with `text` taken off the front
+fn expect_text<'a>(line: &'a str, rest: &'a str, text: &str) -> Result<&'a str, ParseError> {
+    if let Some(rest) = rest.strip_prefix(text) {
+        return Ok(rest);
+    }
+    // point at the first character that differs, showing as much of the line as there was text
+    // left to match
+    let same = rest
+        .char_indices()
+        .zip(text.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(rest.len(), |((i, _), _)| i);
+    let (rest, text) = (&rest[same..], &text[same..]);
+    let len = rest
+        .char_indices()
+        .nth(text.chars().count())
+        .map_or(rest.len(), |(i, _)| i);
+    Err(ParseError::within(line, &rest[..len], &format!("{text:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scan, scan_any};
+
+    const SENSOR: &str = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+
+    #[test]
+    fn captures_parse_in_order() {
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let mut captures = scan(line, SENSOR).unwrap();
+        let values: Vec<i32> = (0..4)
+            .map(|_| captures.parse("an integer").unwrap())
+            .collect();
+        assert_eq!(values, [2, 18, -2, 15]);
+    }
+
+    #[test]
+    fn bad_captures_are_pointed_at() {
+        let line = "Sensor at x=2, y=1o: closest beacon is at x=-2, y=15";
+        let mut captures = scan(line, SENSOR).unwrap();
+        assert_eq!(captures.parse::<i32>("an integer"), Ok(2));
+        let e = captures.parse::<i32>("an integer").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "column 18: expected an integer, found \"1o\""
+        );
+    }
+
+    #[test]
+    fn changed_wording_is_pointed_at() {
+        let line = "Sensor at x=2, y=18: closest beacon at x=-2, y=15";
+        let e = scan(line, SENSOR).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "column 37: expected \"is at x=\", found \"at x=-2,\""
+        );
+
+        let e = scan("Sensor at x=2", SENSOR).unwrap_err();
+        assert_eq!(e.to_string(), "column 14: expected \", y=\", found \"\"");
+
+        let e = scan("Blueprint 1: done.", "Blueprint {}:").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "column 13: expected the end of the line, found \" done.\""
+        );
+    }
+
+    #[test]
+    fn the_first_template_that_fits_wins() {
+        let templates = ["tunnels lead to valves {}", "tunnel leads to valve {}"];
+        let mut captures = scan_any("tunnel leads to valve GG", &templates).unwrap();
+        assert_eq!(captures.text(), "GG");
+
+        let e = scan_any("tunnels lead to vales GG", &templates).unwrap_err();
+        assert_eq!(e.column, Some(20));
+        assert_eq!(e.found, "es G");
+    }
+}
@@ -0,0 +1,319 @@
+//! Searches over state spaces that are only known through a successor function.
+//!
+//! Each search takes a start state and a function giving the states reachable in one move, and
+//! hands back the best path it found along with how much work it took to find it. States need to
+//! be `Clone + Eq + Hash`, since every search remembers the states it has seen and never expands
+//! the same one twice.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How much work a search did.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// states whose successors were generated
+    pub expanded: usize,
+    /// states dropped without expanding them: already seen more cheaply, or (branch and bound)
+    /// unable to beat the best found so far
+    pub pruned: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path<S, C> {
+    /// from the start to the end of the path, both included
+    pub states: Vec<S>,
+    /// the total cost of the moves, or for branch and bound the value of the last state
+    pub cost: C,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Search<S, C> {
+    /// `None` when no goal can be reached
+    pub path: Option<Path<S, C>>,
+    pub stats: Stats,
+}
+
+// every state reached so far, with the index of the one it was reached from, so paths can be
+// rebuilt without each state carrying its own
+struct Tree<S> {
+    nodes: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Tree<S> {
+    fn new(start: S) -> Self {
+        Tree {
+            nodes: vec![(start, None)],
+        }
+    }
+
+    fn add(&mut self, state: S, parent: usize) -> usize {
+        self.nodes.push((state, Some(parent)));
+        self.nodes.len() - 1
+    }
+
+    fn state(&self, i: usize) -> &S {
+        &self.nodes[i].0
+    }
+
+    fn path_to(&self, mut i: usize) -> Vec<S> {
+        let mut states = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            states.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        states.reverse();
+        states
+    }
+}
+
+/// The path with the fewest moves from `start` to a state satisfying `is_goal`.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut stats = Stats::default();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut tree = Tree::new(start);
+    let mut todo = VecDeque::from([(0, 0)]);
+
+    while let Some((moves, i)) = todo.pop_front() {
+        if is_goal(tree.state(i)) {
+            let path = Path {
+                states: tree.path_to(i),
+                cost: moves,
+            };
+            return Search {
+                path: Some(path),
+                stats,
+            };
+        }
+        stats.expanded += 1;
+        for next in successors(tree.state(i)) {
+            if seen.insert(next.clone()) {
+                todo.push_back((moves + 1, tree.add(next, i)));
+            } else {
+                stats.pruned += 1;
+            }
+        }
+    }
+    Search { path: None, stats }
+}
+
+/// The cheapest path from `start` to a state satisfying `is_goal`, where `successors` gives each
+/// next state with the cost of moving to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like `dijkstra`, but trying first the states that `heuristic` says are closest to a goal. The
+/// path is still the cheapest as long as `heuristic` never overestimates the cost left.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut stats = Stats::default();
+    let zero = C::default();
+    // the cheapest known cost of reaching each state, and where in the tree that path ends
+    let mut best = HashMap::from([(start.clone(), (zero, 0))]);
+    let mut todo = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut tree = Tree::new(start);
+
+    while let Some(Reverse((_, cost, i))) = todo.pop() {
+        let state = tree.state(i);
+        if best[state].1 != i {
+            // a cheaper way here turned up after this one was queued
+            stats.pruned += 1;
+            continue;
+        }
+        if is_goal(state) {
+            let path = Path {
+                states: tree.path_to(i),
+                cost,
+            };
+            return Search {
+                path: Some(path),
+                stats,
+            };
+        }
+        stats.expanded += 1;
+        for (next, step) in successors(state).into_iter().collect::<Vec<_>>() {
+            let next_cost = cost + step;
+            if best
+                .get(&next)
+                .is_some_and(|&(known, _)| known <= next_cost)
+            {
+                stats.pruned += 1;
+                continue;
+            }
+            let estimate = next_cost + heuristic(&next);
+            let j = tree.add(next.clone(), i);
+            best.insert(next, (next_cost, j));
+            todo.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+    Search { path: None, stats }
+}
+
+/// The path from `start` to the state with the highest `value`, trying the most valuable states
+/// first and dropping any whose `bound` - the most that any state reachable from it could be
+/// worth - can't beat the best found so far.
+pub fn branch_and_bound<S, V, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut value: impl FnMut(&S) -> V,
+    mut bound: impl FnMut(&S) -> V,
+) -> Search<S, V>
+where
+    S: Clone + Eq + Hash,
+    V: Copy + Ord,
+    I: IntoIterator<Item = S>,
+{
+    let mut stats = Stats::default();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut best = (value(&start), 0);
+    let mut todo = BinaryHeap::from([(best.0, 0)]);
+    let mut tree = Tree::new(start);
+
+    while let Some((_, i)) = todo.pop() {
+        // the best may have got better since this state was queued
+        let state = tree.state(i);
+        if bound(state) <= best.0 {
+            stats.pruned += 1;
+            continue;
+        }
+        stats.expanded += 1;
+        for next in successors(state).into_iter().collect::<Vec<_>>() {
+            if bound(&next) <= best.0 || !seen.insert(next.clone()) {
+                stats.pruned += 1;
+                continue;
+            }
+            let v = value(&next);
+            let j = tree.add(next, i);
+            if v > best.0 {
+                best = (v, j);
+            }
+            todo.push((v, j));
+        }
+    }
+
+    let path = Path {
+        states: tree.path_to(best.1),
+        cost: best.0,
+    };
+    Search {
+        path: Some(path),
+        stats,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, branch_and_bound, dijkstra};
+    use crate::Grid;
+
+    const MAZE: &str = "\
+        S..#....\n\
+        .#.#.##.\n\
+        .#...#..\n\
+        .####.#.\n\
+        ......#G";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, Some, "anything").unwrap()
+    }
+
+    #[test]
+    fn bfs_finds_the_fewest_moves() {
+        let maze = maze();
+        let search = bfs(
+            (0, 0),
+            |&pos| maze.neighbours4(pos).filter(|&p| maze[p] != '#'),
+            |&pos| maze[pos] == 'G',
+        );
+        let path = search.path.unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.states.len(), 16);
+        assert_eq!(path.states[0], (0, 0));
+        assert_eq!(path.states[15], (4, 7));
+        assert!(search.stats.expanded > 0);
+    }
+
+    #[test]
+    fn no_path_to_an_unreachable_goal() {
+        let maze = maze();
+        let search = bfs(
+            (0, 0),
+            |&pos| maze.neighbours4(pos).filter(|&p| maze[p] != '#'),
+            |&pos| pos == (1, 1),
+        );
+        assert_eq!(search.path, None);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree_on_weighted_moves() {
+        // moving down costs 3, any other way 1
+        let maze = maze();
+        let successors = |&pos: &(usize, usize)| {
+            maze.neighbours4(pos)
+                .filter(|&p| maze[p] != '#')
+                .map(move |p| (p, if p.0 > pos.0 { 3 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let goal = |&pos: &(usize, usize)| maze[pos] == 'G';
+        let plain = dijkstra((0, 0), successors, goal);
+        let guided = astar((0, 0), successors, |&(r, c)| (4 - r) * 3 + (7 - c), goal);
+        let cost = plain.path.as_ref().unwrap().cost;
+        assert_eq!(cost, 27);
+        assert_eq!(guided.path.unwrap().cost, cost);
+        assert!(guided.stats.expanded <= plain.stats.expanded);
+    }
+
+    #[test]
+    fn branch_and_bound_finds_the_best_value() {
+        // pick items worth (value, weight) into a knapsack holding 10
+        let items = [(10, 5), (40, 4), (30, 6), (50, 3)];
+        let search = branch_and_bound(
+            (0, 0, 0),
+            |&(i, value, weight)| {
+                let mut next = vec![];
+                if i < items.len() {
+                    next.push((i + 1, value, weight));
+                    let (v, w) = items[i];
+                    if weight + w <= 10 {
+                        next.push((i + 1, value + v, weight + w));
+                    }
+                }
+                next
+            },
+            |&(_, value, _)| value,
+            |&(i, value, _)| value + items[i..].iter().map(|(v, _)| v).sum::<i32>(),
+        );
+        let path = search.path.unwrap();
+        assert_eq!(path.cost, 90);
+        assert_eq!(path.states.last(), Some(&(4, 90, 7)));
+        assert!(search.stats.pruned > 0);
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::{Answer, Params, ParseError};
+
+/// One day's puzzle: parse the input once, then solve either part from the parsed form.
+pub trait Solution {
+    const DAY: u8;
+
+    /// The puzzle input after parsing.
+    type Input;
+    type Answer1: IntoAnswer;
+    type Answer2: IntoAnswer;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Self::Answer1;
+    fn part_2(input: &Self::Input) -> Self::Answer2;
+
+    /// The names of the puzzle parameters this day reads in `with_params`.
+    const PARAMS: &'static [&'static str] = &[];
+
+    /// Sets the puzzle parameters that aren't part of the input text (a row number, a grid
+    /// size, ...) from `params`. `parse` fills in the values for the real input, so only the ones
+    /// that are given change.
+    fn with_params(input: Self::Input, _params: &Params) -> Result<Self::Input, ParseError> {
+        Ok(input)
+    }
+}
+
+/// Answer for a part that only has a solution in another language.
+#[derive(Clone, Copy, Debug)]
+pub struct Unsolved(pub &'static str);
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "not solved in rust, see {}", self.0)
+    }
+}
+
+/// Why a part has no answer for an input that parsed fine, such as a parameter that leaves
+/// nowhere to look.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NoAnswer(pub String);
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// What a part returns: an answer, or for parts that don't always have one, a `Result` of one.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, NoAnswer>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, NoAnswer> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, NoAnswer> {
+    fn into_answer(self) -> Result<Answer, NoAnswer> {
+        self.map(Into::into)
+    }
+}
+
+/// A `Solution` with its types erased, so every day can be driven the same way.
+pub trait Solver {
+    /// Parses `s` and sets the puzzle parameters in `params`, which must all be ones the day
+    /// reads.
+    fn parse(&self, s: &str, params: &Params) -> Result<Box<dyn Parsed>, ParseError>;
+}
+
+/// A parsed input, ready to be solved.
+pub trait Parsed {
+    /// Solves `part` (1 or 2).
+    fn solve(&self, part: u8) -> Result<Answer, NoAnswer>;
+}
+
+struct Erased<S>(PhantomData<S>);
+
+struct Input<S: Solution>(S::Input);
+
+impl<S: Solution + 'static> Solver for Erased<S> {
+    fn parse(&self, s: &str, params: &Params) -> Result<Box<dyn Parsed>, ParseError> {
+        if let Some(key) = params.keys().find(|key| !S::PARAMS.contains(key)) {
+            let expected = match S::PARAMS {
+                [] => "no parameters".to_string(),
+                names => format!("a parameter ({})", names.join(", ")),
+            };
+            return Err(ParseError::new(key, &expected).in_day(S::DAY));
+        }
+        let input = S::parse(s).and_then(|input| S::with_params(input, params));
+        Ok(Box::new(Input::<S>(input.map_err(|e| e.in_day(S::DAY))?)))
+    }
+}
+
+impl<S: Solution> Parsed for Input<S> {
+    fn solve(&self, part: u8) -> Result<Answer, NoAnswer> {
+        match part {
+            1 => S::part_1(&self.0).into_answer(),
+            2 => S::part_2(&self.0).into_answer(),
+            _ => panic!("there is no part {part}"),
+        }
+    }
+}
+
+pub fn solver<S: Solution + 'static>() -> Box<dyn Solver> {
+    Box::new(Erased::<S>(PhantomData))
+}
@@ -1,7 +1,13 @@
+import sys
+
+# the input is read from the file named on the command line (- for stdin), or input.txt
+input_file = sys.argv[1] if len(sys.argv) > 1 else "input.txt"
+lines = list(open(0 if input_file == "-" else input_file))
+
 # part 1
 max_index = max_cals = 0
 elves = [0]
-for line in open("input.txt"):
+for line in lines:
     l = line.strip()
     if l.isnumeric():
         elves[-1] += int(l)
@@ -11,15 +17,15 @@ for line in open("input.txt"):
     if elves[-1] > max_cals:
         max_cals = elves[-1]
         max_index = len(elves) - 1
-print("Part 1", max_cals)
+print("Part 1:", max_cals)
 
 # part 2
 elves = [0]
-for line in open("input.txt"):
+for line in lines:
     l = line.strip()
     if l.isnumeric():
         elves[-1] += int(l)
     else:
         elves.append(0)
 elves = sorted(elves)
-print("Part 2", sum(elves[-3:]))
+print("Part 2:", sum(elves[-3:]))
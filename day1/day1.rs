@@ -1,27 +1,38 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+pub mod generate;
 
-fn main() {
-    if let Ok(lines) = read_lines("./input.txt") {
+use aoc_common::{numbered_lines, ParseError, Solution};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 1;
+
+    // calories carried by each elf, smallest to largest
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
         let mut elves = vec![0];
-        for line in lines {
-            if let Ok(cals) = line.unwrap().parse::<usize>() {
-                *elves.last_mut().unwrap() += cals;
-            } else {
+        for (n, line) in numbered_lines(s) {
+            if line.is_empty() {
                 elves.push(0);
+            } else {
+                let cals = line.parse::<usize>().map_err(|_| {
+                    ParseError::new(line, "a calorie count or a blank line").on_line(n)
+                })?;
+                *elves.last_mut().unwrap() += cals;
             }
         }
         elves.sort();
-        println!("Part 1: {:?}", elves.last().unwrap());
-        println!("Part 2: {:?}", elves.iter().rev().take(3).sum::<usize>());
+        Ok(elves)
     }
-}
 
-// from rust by example
-// https://doc.rust-lang.org/rust-by-example/std_misc/file/read_lines.html
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    fn part_1(elves: &Self::Input) -> usize {
+        *elves.last().unwrap()
+    }
+
+    fn part_2(elves: &Self::Input) -> usize {
+        elves.iter().rev().take(3).sum::<usize>()
+    }
 }
@@ -0,0 +1,19 @@
+//! Random calorie lists.
+
+use aoc_common::rng::Rng;
+
+/// About as many elves as a real input has.
+pub const SIZE: usize = 250;
+
+/// A list of the snacks carried by `size` elves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let snacks = rng.range(1..=15usize);
+            (0..snacks)
+                .map(|_| format!("{}\n", rng.range(1000..=60_000u32)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
@@ -0,0 +1,25 @@
+//! Random CRT programs.
+
+use aoc_common::rng::Rng;
+
+/// The number of cycles it takes a real program to draw the screen.
+pub const SIZE: usize = 240;
+
+/// A program that runs for at least `size` cycles, keeping the sprite on the screen.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut program = String::new();
+    let (mut cycles, mut x) = (0, 1);
+    while cycles < size.max(1) {
+        if rng.chance(0.3) {
+            program.push_str("noop\n");
+            cycles += 1;
+        } else {
+            let to = rng.range(0..=39isize);
+            let v = (to - x).clamp(-20, 20);
+            program.push_str(&format!("addx {v}\n"));
+            x += v;
+            cycles += 2;
+        }
+    }
+    program
+}
@@ -1,7 +1,13 @@
+pub mod generate;
+
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::fmt;
 use std::str::FromStr;
 
+use aoc_common::render::{self, Colour, Glyph};
+use aoc_common::{numbered_lines, trace, Bitmap, Grid, ParseError, Solution};
+
 #[derive(Debug)]
 struct Computer {
     instructions: VecDeque<Instruction>,
@@ -14,11 +20,11 @@ struct Computer {
 impl Computer {
     fn new(instructions: VecDeque<Instruction>) -> Self {
         Computer {
-            instructions, 
+            instructions,
             i: None,
             i_ctr: 0,
             cycle: 0,
-            x: 1
+            x: 1,
         }
     }
 
@@ -30,31 +36,29 @@ impl Computer {
         if self.i_ctr > 0 {
             self.i_ctr -= 1;
             self.cycle += 1;
-            return true
+            return true;
         }
         false
     }
 
     fn process_instruction(&mut self) {
         // process the current instruction
-        match self.i {
-            Some(Instruction::AddX(v)) => self.x += v,
-            _ => (),
+        if let Some(Instruction::AddX(v)) = self.i {
+            self.x += v;
         }
 
         // grab the next instruction
         self.i = self.instructions.pop_front();
 
         // reset the instruction counter
-        match &self.i {
-            Some(inst) => self.i_ctr = inst.cycles(),
-            _ => (),
+        if let Some(inst) = &self.i {
+            self.i_ctr = inst.cycles();
         }
     }
 }
 
-#[derive(Debug)]
-enum Instruction {
+#[derive(Clone, Debug)]
+pub enum Instruction {
     AddX(isize),
     NoOp,
 }
@@ -69,30 +73,51 @@ impl Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "noop" { return Ok(Instruction::NoOp); }
+        if s == "noop" {
+            return Ok(Instruction::NoOp);
+        }
 
         match s.split_once(' ') {
-            Some(("addx", v)) => Ok(Instruction::AddX(v.parse().map_err(|_| "not a valid value")?)),
-            _ => Err("wtf - not a recognized command".to_string()),
+            Some(("addx", v)) => Ok(Instruction::AddX(
+                v.parse()
+                    .map_err(|_| ParseError::within(s, v, "an integer"))?,
+            )),
+            _ => Err(ParseError::new(s, "noop or addx <int>")),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::AddX(v) => write!(f, "addx {v}"),
+            Instruction::NoOp => write!(f, "noop"),
         }
     }
 }
 
-fn part_1(s: &str) -> isize {
-    let instructions = s
-        .lines()
-        .map(|x| x.parse::<Instruction>().expect("failed to parse an instruction"))
-        .collect::<VecDeque<_>>();
-    let mut c = Computer::new(instructions);
+pub fn parse(s: &str) -> Result<VecDeque<Instruction>, ParseError> {
+    numbered_lines(s)
+        .map(|(n, x)| x.parse::<Instruction>().map_err(|e| e.on_line(n)))
+        .collect()
+}
+
+pub fn part_1(instructions: &VecDeque<Instruction>) -> isize {
+    let mut c = Computer::new(instructions.clone());
 
     let mut interesting = HashSet::from([20, 60, 100, 140, 180, 220]);
     let mut result = 0;
     while c.step() && !interesting.is_empty() {
         if interesting.remove(&c.cycle) {
-            result += dbg!(&c.cycle * &c.x);
+            trace!(
+                "signal strength during cycle {}: {}",
+                c.cycle,
+                c.cycle * c.x
+            );
+            result += c.cycle * c.x;
         }
     }
 
@@ -100,38 +125,76 @@ fn part_1(s: &str) -> isize {
 }
 
 const WIDTH: isize = 40;
+const HEIGHT: usize = 6;
+
+// the pixels drawn so far, and the sprite on the row being drawn as `(row, x)`
+fn draw_crt(crt: &str, sprite: Option<(usize, isize)>) -> String {
+    let rows: Vec<&[u8]> = crt.lines().map(str::as_bytes).collect();
+    render::picture(WIDTH as usize, HEIGHT, |(row, col)| {
+        match rows.get(row).and_then(|pixels| pixels.get(col)) {
+            Some(b'#') => Glyph::coloured('#', Colour::Green),
+            Some(_) => Glyph::coloured('.', Colour::Grey),
+            None => match sprite {
+                Some((r, x)) if r == row && (x - 1..=x + 1).contains(&(col as isize)) => {
+                    Glyph::coloured('=', Colour::Yellow)
+                }
+                _ => Glyph::plain(' '),
+            },
+        }
+    })
+}
 
-fn part_2(s: &str) {
-    let instructions = s
-        .lines()
-        .map(|x| x.parse::<Instruction>().expect("failed to parse an instruction"))
-        .collect::<VecDeque<_>>();
-    let mut c = Computer::new(instructions);
+// the answer is whatever letters get drawn on the CRT
+pub fn part_2(instructions: &VecDeque<Instruction>) -> Bitmap {
+    let mut c = Computer::new(instructions.clone());
+    let mut crt = String::new();
 
     while c.step() {
-        let h = &c.cycle % WIDTH;
-        if h == 1 { print!("Cycle {:4} -> ", &c.cycle); }
+        let h = c.cycle % WIDTH;
         // cycles are 1-indexed, but pixels are 0-indexed
-        if (&c.x - 1..=&c.x + 1).contains(&(h - 1)) {
-            // print!("\x1b[93m#\x1b[0m");
-            // make it a bit more visible
-            print!("\x1b[0;0;100m#\x1b[0m");
+        if (c.x - 1..=c.x + 1).contains(&(h - 1)) {
+            crt.push('#');
         } else {
-            print!(".");
+            crt.push('.');
+        }
+        if h == 0 {
+            crt.push('\n');
+        }
+        let row = (c.cycle / WIDTH) as usize;
+        render::frame(&format!("cycle {}", c.cycle), || {
+            draw_crt(&crt, Some((row, c.x)))
+        });
+    }
+    render::show("CRT", || draw_crt(&crt, None));
+
+    let rows: Vec<&str> = crt.lines().collect();
+    let mut pixels = Grid::new(WIDTH as usize, rows.len(), false);
+    for (row, line) in rows.iter().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            pixels[(row, col)] = c == '#';
         }
-        if h == 0 { println!(" <- Cycle {:4}", &c.cycle); }
     }
+    Bitmap::new(pixels)
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 10;
 
-fn main() {
-    assert!(dbg!(part_1(include_str!("test.input.txt"))) == 13140);
-    println!("Part 1: {}", part_1(include_str!("input.txt")));
+    type Input = VecDeque<Instruction>;
+    type Answer1 = isize;
+    type Answer2 = Bitmap;
 
-    println!("");
-    println!("Part 2: test input");
-    part_2(include_str!("test.input.txt"));
-    println!("");
-    println!("Part 2: real input");
-    part_2(include_str!("input.txt"));
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part_1(instructions: &Self::Input) -> isize {
+        part_1(instructions)
+    }
+
+    fn part_2(instructions: &Self::Input) -> Bitmap {
+        part_2(instructions)
+    }
 }
@@ -0,0 +1,24 @@
+use aoc_common::Solution;
+use day10::Puzzle;
+
+const EXAMPLE: &str = include_str!("../test.input.txt");
+
+#[test]
+fn part_1() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_1(&input), 13140);
+}
+
+#[test]
+fn part_2() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    let screen = [
+        "##..##..##..##..##..##..##..##..##..##..",
+        "###...###...###...###...###...###...###.",
+        "####....####....####....####....####....",
+        "#####.....#####.....#####.....#####.....",
+        "######......######......######......###.",
+        "#######.......#######.......#######.....",
+    ];
+    assert_eq!(Puzzle::part_2(&input).drawing(), screen.join("\n"));
+}
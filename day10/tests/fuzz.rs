@@ -0,0 +1,7 @@
+use aoc_common::fuzz;
+use day10::{generate, Instruction, Puzzle};
+
+#[test]
+fn programs_parse_and_round_trip() {
+    fuzz::check::<Puzzle, Instruction>(env!("CARGO_MANIFEST_DIR"), generate::generate);
+}
@@ -0,0 +1,34 @@
+//! Random cave scans.
+
+use aoc_common::rng::Rng;
+
+/// About as many paths as a real scan has.
+pub const SIZE: usize = 150;
+
+// the highest rock in a real scan is about this far below the source
+const TOP: isize = 13;
+
+/// A scan of `size` paths of rock, each a few straight lines joined end to end. Like a real
+/// scan's, they keep well below the sand's source at 500,0.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut point = (rng.range(440..=560isize), rng.range(TOP..=170isize));
+            let mut points = vec![point];
+            // alternate between across and down (or up), starting either way
+            let mut across = rng.chance(0.5);
+            for _ in 0..rng.range(1..=5usize) {
+                let step = rng.range(1..=10isize) * if rng.chance(0.5) { 1 } else { -1 };
+                point = if across {
+                    (point.0 + step, point.1)
+                } else {
+                    (point.0, (point.1 + step).max(TOP))
+                };
+                points.push(point);
+                across = !across;
+            }
+            let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
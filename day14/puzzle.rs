@@ -1,106 +1,284 @@
-use std::collections::HashSet;
-use std::convert::TryFrom;
-
-fn parse_input(s: &str) -> HashSet<(usize, usize)> {
-    let mut rock: HashSet<(usize, usize)> = HashSet::new();
-
-    s.lines().for_each(|line| {
-        line.split(" -> ")
-            .collect::<Vec<&str>>()
-            .windows(2)
-            .map(|x| <&[&str; 2]>::try_from(x).expect("line may be too short?"))
-            .for_each(|&[s, e]| {
-                // unwrap all the things
-                let (x_start, y_start) = s.split_once(',').unwrap();
-                let x_start: usize = x_start.parse().unwrap();
-                let y_start: usize = y_start.parse().unwrap();
-
-                let (x_end, y_end) = e.split_once(',').unwrap();
-                let x_end: usize = x_end.parse().unwrap();
-                let y_end: usize = y_end.parse().unwrap();
-
-                let (x_min, x_max) = (x_start.min(x_end), x_start.max(x_end));
-                let (y_min, y_max) = (y_start.min(y_end), y_start.max(y_end));
-
-                assert!(x_min == x_max || y_min == y_max);
-
-                for x in x_min..=x_max {
-                    for y in y_min..=y_max {
-                        rock.insert((x, y));
-                    }
+pub mod generate;
+
+use std::fmt;
+use std::ops::{Index, IndexMut, RangeInclusive};
+use std::str::FromStr;
+
+use aoc_common::grid::Pos;
+use aoc_common::image::{self, Image, Palette, Rgb};
+use aoc_common::render::{self, Colour, Glyph};
+use aoc_common::{debug, numbered_lines, Grid, ParseError, Point2, Solution};
+
+// how far from the source the scan can reach, which keeps the cave a sensible size
+const REACH: isize = 1000;
+
+// `point` is part of `line`, which columns in errors are relative to
+fn parse_point(line: &str, point: &str) -> Result<Point2, ParseError> {
+    let (x, y) = point
+        .split_once(',')
+        .ok_or_else(|| ParseError::within(line, point, "<x>,<y>"))?;
+    let coord = |v: &str, range: RangeInclusive<isize>, expected: &str| {
+        v.parse::<isize>()
+            .ok()
+            .filter(|c| range.contains(c))
+            .ok_or_else(|| ParseError::within(line, v, expected))
+    };
+    let y = coord(
+        y,
+        0..=REACH,
+        &format!("a y at or below the source, 0 to {REACH}"),
+    )?;
+    let x = coord(
+        x,
+        SOURCE.x - REACH..=SOURCE.x + REACH,
+        &format!("an x within {REACH} of the source's {}", SOURCE.x),
+    )?;
+    Ok(Point2::new(x, y))
+}
+
+/// A path of rock from the scan: points joined by straight lines, across or down.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path(pub Vec<Point2>);
+
+impl Path {
+    /// Every point of rock along the path (the corners twice).
+    fn rock(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.0.windows(2).flat_map(|pair| {
+            let [start, stop] = *pair else {
+                unreachable!("windows of 2");
+            };
+            // walk one step at a time from start to stop
+            let d = stop - start;
+            let step = Point2::new(d.x.signum(), d.y.signum());
+            let steps = d.x.abs().max(d.y.abs());
+            (0..=steps).map(move |i| start + step * i)
+        })
+    }
+}
+
+impl FromStr for Path {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut points: Vec<Point2> = vec![];
+        for point in s.split(" -> ") {
+            let p = parse_point(s, point)?;
+            if let Some(prev) = points.last() {
+                if prev.x != p.x && prev.y != p.y {
+                    return Err(ParseError::within(
+                        s,
+                        point,
+                        "a point in line with the previous one",
+                    ));
                 }
-            });
-    });
+            }
+            points.push(p);
+        }
+        Ok(Path(points))
+    }
+}
 
-    rock
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, p) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{p}")?;
+        }
+        Ok(())
+    }
 }
 
-fn so_are_the_days_of_our_lives(
-    cur: &(usize, usize),
-    rock: &HashSet<(usize, usize)>,
-) -> (usize, usize) {
-    if !rock.contains(&(cur.0, cur.1 + 1)) {
-        // straight down
-        (cur.0, cur.1 + 1)
-    } else if !rock.contains(&(cur.0 - 1, cur.1 + 1)) {
-        // else to the left
-        (cur.0 - 1, cur.1 + 1)
-    } else if !rock.contains(&(cur.0 + 1, cur.1 + 1)) {
-        // else to the right
-        (cur.0 + 1, cur.1 + 1)
-    } else {
-        *cur
+pub fn parse_input(s: &str) -> Result<Cave, ParseError> {
+    let mut rock = vec![];
+    for (n, line) in numbered_lines(s) {
+        let path: Path = line.parse().map_err(|e: ParseError| e.on_line(n))?;
+        rock.extend(path.rock());
+    }
+
+    if rock.is_empty() {
+        return Err(ParseError::new("", "at least one path of rock"));
     }
+    Ok(Cave::new(&rock))
 }
 
-const SOURCE: (usize, usize) = (500, 0);
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        };
+        write!(f, "{c}")
+    }
+}
 
-fn part_1(s: &str) -> usize {
-    let mut rock = parse_input(s);
-    let max_rock = dbg!(rock.iter().max_by_key(|(_x, y)| y).expect("no rocks").1);
+// air, rock, sand, and the source of the sand
+const PALETTE: [Rgb; 4] = [
+    Rgb::hex(0x0f0f23),
+    Rgb::hex(0x8c8c8c),
+    Rgb::hex(0xffcc33),
+    Rgb::hex(0xff3333),
+];
+
+/// The slice of the cave the sand can reach, as a grid with a row per y and a column per x from
+/// `left`.
+#[derive(Clone, Debug)]
+pub struct Cave {
+    tiles: Grid<Tile>,
+    left: isize,
+    lowest_rock: isize,
+}
+
+impl Cave {
+    fn new(rock: &[Point2]) -> Cave {
+        let lowest_rock = rock.iter().map(|p| p.y).max().expect("no rocks");
+        // the grid has room for the floor, and the sand piles up no wider than its depth either
+        // side of the source, or falls off just past the outermost rock
+        let floor = lowest_rock + 2;
+        let x_min = rock.iter().map(|p| p.x).min().expect("no rocks");
+        let x_max = rock.iter().map(|p| p.x).max().expect("no rocks");
+        let left = x_min.min(SOURCE.x - floor) - 1;
+        let right = x_max.max(SOURCE.x + floor) + 1;
+
+        let tiles = Grid::new((right - left + 1) as usize, floor as usize + 1, Tile::Air);
+        let mut cave = Cave {
+            tiles,
+            left,
+            lowest_rock,
+        };
+        for &p in rock {
+            cave[p] = Tile::Rock;
+        }
+        cave
+    }
+
+    fn pos(&self, p: Point2) -> Pos {
+        (p.y as usize, (p.x - self.left) as usize)
+    }
+
+    fn image(&self, palette: &Palette) -> Image {
+        let source = self.pos(SOURCE);
+        Image::from_grid(&self.tiles, palette, &PALETTE, |pos, tile| match tile {
+            Tile::Air if pos == source => 3,
+            Tile::Air => 0,
+            Tile::Rock => 1,
+            Tile::Sand => 2,
+        })
+    }
+
+    fn draw(&self) -> String {
+        let source = self.pos(SOURCE);
+        render::grid(&self.tiles, |pos, tile| match tile {
+            Tile::Air if pos == source => Glyph::coloured('+', Colour::Red),
+            Tile::Air => Glyph::coloured('.', Colour::Grey),
+            Tile::Rock => Glyph::coloured('#', Colour::White),
+            Tile::Sand => Glyph::coloured('o', Colour::Yellow),
+        })
+    }
+}
+
+impl Index<Point2> for Cave {
+    type Output = Tile;
+
+    fn index(&self, p: Point2) -> &Tile {
+        &self.tiles[self.pos(p)]
+    }
+}
+
+impl IndexMut<Point2> for Cave {
+    fn index_mut(&mut self, p: Point2) -> &mut Tile {
+        let pos = self.pos(p);
+        &mut self.tiles[pos]
+    }
+}
+
+// straight down, else to the left, else to the right
+const FALLS: [Point2; 3] = [
+    Point2 { x: 0, y: 1 },
+    Point2 { x: -1, y: 1 },
+    Point2 { x: 1, y: 1 },
+];
+
+fn so_are_the_days_of_our_lives(cur: Point2, cave: &Cave) -> Point2 {
+    FALLS
+        .iter()
+        .map(|&fall| cur + fall)
+        .find(|&next| cave[next] == Tile::Air)
+        .unwrap_or(cur)
+}
+
+const SOURCE: Point2 = Point2 { x: 500, y: 0 };
+
+pub fn part_1(cave: &Cave) -> usize {
+    let mut cave = cave.clone();
+    let max_rock = cave.lowest_rock;
+    debug!("lowest rock at y = {max_rock}");
 
     let mut sand_at_rest = 0;
     let mut cur = SOURCE;
     let mut prev = cur;
     loop {
         // let the sand fall
-        cur = so_are_the_days_of_our_lives(&cur, &rock);
+        cur = so_are_the_days_of_our_lives(cur, &cave);
 
         // falling into the ether
-        if cur.1 >= max_rock {
+        if cur.y >= max_rock {
             break;
         }
 
         // come to rest
         if cur == prev {
             // sand might as well be a rock at this point
-            rock.insert(cur);
+            cave[cur] = Tile::Sand;
             sand_at_rest += 1;
+            render::frame(&format!("{sand_at_rest} grains of sand"), || cave.draw());
+            image::frame("day14-abyss", |palette| cave.image(palette));
+            // the rock can hold up a pile that reaches the source, and then nothing more falls
+            if cur == SOURCE {
+                break;
+            }
             // respawn
             cur = SOURCE;
         }
         prev = cur;
     }
 
+    render::show("the sand falling into the abyss", || cave.draw());
+    image::save("day14-abyss", |palette| cave.image(palette));
     sand_at_rest
 }
 
-fn part_2(s: &str) -> usize {
-    let mut rock = parse_input(s);
-    let floor = dbg!(2 + rock.iter().max_by_key(|(_x, y)| y).expect("no rocks").1);
+pub fn part_2(cave: &Cave) -> usize {
+    let mut cave = cave.clone();
+    let floor = cave.lowest_rock + 2;
+    debug!("floor at y = {floor}");
+    for x in cave.left..cave.left + cave.tiles.width() as isize {
+        cave[Point2::new(x, floor)] = Tile::Rock;
+    }
 
     let mut sand_at_rest = 0;
     let mut cur = SOURCE;
     let mut prev = cur;
     loop {
         // let the sand fall
-        cur = so_are_the_days_of_our_lives(&cur, &rock);
+        cur = so_are_the_days_of_our_lives(cur, &cave);
 
         // come to rest
-        if cur == prev || cur.1 == floor - 1 {
+        if cur == prev {
             // sand might as well be a rock at this point
-            rock.insert(cur);
+            cave[cur] = Tile::Sand;
             sand_at_rest += 1;
+            render::frame(&format!("{sand_at_rest} grains of sand"), || cave.draw());
+            image::frame("day14-floor", |palette| cave.image(palette));
             // full up!
             if cur == SOURCE {
                 break;
@@ -110,15 +288,29 @@ fn part_2(s: &str) -> usize {
         }
         prev = cur;
     }
+    render::show("the sand blocking the source", || cave.draw());
+    image::save("day14-floor", |palette| cave.image(palette));
     sand_at_rest
 }
 
-fn main() {
-    assert!(dbg!(part_1(include_str!("test.input.txt"))) == 24);
-    let part_1_answer = part_1(include_str!("input.txt"));
-    println!("Part 1: {part_1_answer}");
+pub struct Puzzle;
 
-    assert!(dbg!(part_2(include_str!("test.input.txt"))) == 93);
-    let part_2_answer = part_2(include_str!("input.txt"));
-    println!("Part 2: {part_2_answer}");
+impl Solution for Puzzle {
+    const DAY: u8 = 14;
+
+    type Input = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse_input(s)
+    }
+
+    fn part_1(rock: &Self::Input) -> usize {
+        part_1(rock)
+    }
+
+    fn part_2(rock: &Self::Input) -> usize {
+        part_2(rock)
+    }
 }
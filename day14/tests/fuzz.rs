@@ -0,0 +1,7 @@
+use aoc_common::fuzz;
+use day14::{generate, Path, Puzzle};
+
+#[test]
+fn scans_parse_and_round_trip() {
+    fuzz::check::<Puzzle, Path>(env!("CARGO_MANIFEST_DIR"), generate::generate);
+}
@@ -0,0 +1,38 @@
+//! Random sensor reports.
+
+use aoc_common::rng::Rng;
+
+/// About as many sensors as a real report has.
+pub const SIZE: usize = 30;
+
+// the search area in part 2 is 0 to this in both directions
+const BOUND: isize = 4_000_000;
+
+/// A report from `size` sensors spread around the real search area, with a spot inside it that
+/// none of them cover so there's always somewhere for the distress beacon to be.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let hidden = (rng.range(0..=BOUND), rng.range(0..=BOUND));
+    let spread = -BOUND / 8..=BOUND + BOUND / 8;
+    (0..size.max(1))
+        .map(|_| {
+            let sensor = loop {
+                let sensor = (rng.range(spread.clone()), rng.range(spread.clone()));
+                if sensor != hidden {
+                    break sensor;
+                }
+            };
+            // the closest beacon is nearer than the hidden spot, so it can't rule that out
+            let reach = (sensor.0 - hidden.0).abs() + (sensor.1 - hidden.1).abs();
+            let dist = rng.range(reach / 2..reach);
+            let dx = rng.range(-dist..=dist);
+            let dy = (dist - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sensor.0,
+                sensor.1,
+                sensor.0 + dx,
+                sensor.1 + dy
+            )
+        })
+        .collect()
+}
@@ -1,141 +1,159 @@
-use std::collections::{HashMap, HashSet};
-use std::ops::Sub;
-use std::str::FromStr;
-
-const CHARS_TO_KEEP: [char; 13] = [
-    ',', ':', '-', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
-];
+pub mod generate;
+
+use aoc_common::image::{self, Image, Palette, Rgb};
+use aoc_common::scan::scan;
+use aoc_common::{
+    debug, numbered_lines, IntervalSet, NoAnswer, Params, ParseError, Point2, Solution,
+};
+
+const SENSOR: &str = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+
+fn parse_line(line: &str) -> Result<(Point2, Point2), ParseError> {
+    let mut captures = scan(line, SENSOR)?;
+    let mut point = || -> Result<Point2, ParseError> {
+        Ok(Point2::new(
+            captures.parse("an integer")?,
+            captures.parse("an integer")?,
+        ))
+    };
+    Ok((point()?, point()?))
+}
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Loc(isize, isize);
+pub fn parse(s: &str) -> Result<Vec<(Point2, Point2)>, ParseError> {
+    numbered_lines(s)
+        .map(|(n, line)| parse_line(line).map_err(|e| e.on_line(n)))
+        .collect()
+}
 
-impl Sub for Loc {
-    type Output = isize;
+// the stretch of row `y` each sensor can see, all together
+fn coverage(locs: &[(Point2, Point2)], y: isize) -> IntervalSet {
+    locs.iter()
+        .map(|&(s, b)| {
+            let reach = s.manhattan(b) - (s.y - y).abs();
+            s.x - reach..=s.x + reach
+        })
+        .collect()
+}
 
-    fn sub(self, other: Self) -> Self::Output {
-        (self.0 - other.0).abs() + (self.1 - other.1).abs()
+pub fn part_1(locs: &[(Point2, Point2)], y_loc: isize) -> isize {
+    let mut no_beacons = coverage(locs, y_loc);
+    for &(_, b) in locs.iter().filter(|(_, b)| b.y == y_loc) {
+        no_beacons.remove(b.x..=b.x);
     }
+    no_beacons.len()
 }
 
-fn parse(s: &str) -> Vec<(Loc, Loc)> {
-    s.lines()
-        .map(|line| {
-            line.chars()
-                .filter(|x| CHARS_TO_KEEP.contains(x))
-                .collect::<String>()
-        })
-        .map(|x| {
-            let (s, b) = x.split_once(':').expect("wtf?");
-            let (sx, sy) = s.split_once(',').expect("bad sensor");
-            let s = Loc(isize::from_str(sx).unwrap(), isize::from_str(sy).unwrap());
-            let (bx, by) = b.split_once(',').expect("bad beacon");
-            let b = Loc(isize::from_str(bx).unwrap(), isize::from_str(by).unwrap());
-            (s, b)
-        })
-        .collect::<Vec<_>>()
-}
+// nowhere a sensor can see, somewhere the sensors with even and odd numbers can see, a sensor, a
+// beacon, and the distress beacon
+const PALETTE: [Rgb; 6] = [
+    Rgb::hex(0x0f0f23),
+    Rgb::hex(0x2d4a7a),
+    Rgb::hex(0x3a5f99),
+    Rgb::hex(0xffffff),
+    Rgb::hex(0xffcc33),
+    Rgb::hex(0xff3333),
+];
 
-fn part_1(locs: Vec<(Loc, Loc)>, y_loc: isize) -> isize {
-    let mut sensors: HashMap<Loc, (isize, Loc)> = HashMap::new();
-    let mut beacons: HashSet<Loc> = HashSet::new();
-    let mut no_beacons: HashSet<Loc> = HashSet::new();
-    locs.into_iter().for_each(|(s, b)| {
-        sensors.insert(s, (s - b, b));
-        beacons.insert(b);
+// the search area is millions of positions across, so each pixel stands for a square of them,
+// small enough that the picture is no more than this many pixels across
+const MOST_PIXELS: isize = 400;
+
+fn coverage_image(
+    locs: &[(Point2, Point2)],
+    max_grid: isize,
+    distress: Point2,
+    palette: &Palette,
+) -> Image {
+    let per_pixel = (max_grid + 1 + MOST_PIXELS - 1) / MOST_PIXELS;
+    let size = ((max_grid + per_pixel) / per_pixel) as usize;
+    let mut image = Image::from_cells(size, size, palette, &PALETTE, |(row, col)| {
+        let p = Point2::new(col as isize * per_pixel, row as isize * per_pixel);
+        locs.iter()
+            .position(|&(s, b)| p.manhattan(s) <= s.manhattan(b))
+            .map_or(0, |i| 1 + i % 2)
     });
-
-    // this is still pretty slow but it works - only look at the row we care about
-    sensors.iter().for_each(|(s, (r, _b))| {
-        for x in 0..*r {
-            let loc = Loc(s.0 - x as isize, y_loc);
-            if loc - *s <= *r && !sensors.contains_key(&loc) && !beacons.contains(&loc) {
-                no_beacons.insert(loc);
-            }
-            let loc = Loc(s.0 + x as isize, y_loc);
-            if loc - *s <= *r && !sensors.contains_key(&loc) && !beacons.contains(&loc) {
-                no_beacons.insert(loc);
-            }
+    let pixel = |p: Point2| ((p.y / per_pixel) as usize, (p.x / per_pixel) as usize);
+    for &(s, b) in locs {
+        if (0..=max_grid).contains(&s.x) && (0..=max_grid).contains(&s.y) {
+            image.set(pixel(s), palette.colour(3, &PALETTE));
         }
-    });
-
-    // splat the sensor grids into the no_beacons set
-    // this takes way too fuckin' long
-    /*
-    sensors.iter().for_each(|(s, (r, b))| {
-        dbg!(r);
-        for x in 0..=*r {
-            for y in 0..=*r {
-                let loc = Loc(s.0 - x as isize, s.1 - y as isize);
-                if loc - *s <= *r && !sensors.contains_key(&loc) && !beacons.contains(&loc) {
-                    no_beacons.insert(loc);
-                }
-
-                let loc = Loc(s.0 + x as isize, s.1 - y as isize);
-                if loc - *s <= *r && !sensors.contains_key(&loc) && !beacons.contains(&loc) {
-                    no_beacons.insert(loc);
-                }
-
-                let loc = Loc(s.0 - x as isize, s.1 + y as isize);
-                if loc - *s <= *r && !sensors.contains_key(&loc) && !beacons.contains(&loc) {
-                    no_beacons.insert(loc);
-                }
-
-                let loc = Loc(s.0 + x as isize, s.1 + y as isize);
-                if loc - *s <= *r && !sensors.contains_key(&loc) && !beacons.contains(&loc) {
-                    no_beacons.insert(loc);
-                }
-            }
+        if (0..=max_grid).contains(&b.x) && (0..=max_grid).contains(&b.y) {
+            image.set(pixel(b), palette.colour(4, &PALETTE));
         }
+    }
+    image.set(pixel(distress), palette.colour(5, &PALETTE));
+    image
+}
+
+pub fn part_2(locs: &[(Point2, Point2)], max_grid: isize) -> Result<isize, NoAnswer> {
+    // only one spot in the whole area is out of sight of every sensor, though a bound other than
+    // the puzzle's can leave none
+    let (x, y) = (0..=max_grid)
+        .find_map(|y| {
+            let gap = coverage(locs, y).gaps(0..=max_grid).next()?;
+            Some((*gap.start(), y))
+        })
+        .ok_or_else(|| {
+            NoAnswer(format!(
+                "every spot from 0 to {max_grid} (the bound) is in range of a sensor"
+            ))
+        })?;
+
+    debug!("distress beacon at x = {x}, y = {y}");
+    image::save("day15-coverage", |palette| {
+        coverage_image(locs, max_grid, Point2::new(x, y), palette)
     });
-    */
+    Ok(x * 4_000_000 + y)
+}
 
-    dbg!(no_beacons.iter().filter(|Loc(_x, y)| *y == y_loc).count() as isize)
+// the row to check in part 1 and the extent of the search area in part 2 aren't part of the
+// input, and they're much smaller for the example; they're the `row` and `bound` parameters
+#[derive(Debug)]
+pub struct Report {
+    pub locs: Vec<(Point2, Point2)>,
+    pub row: isize,
+    pub max_grid: isize,
 }
 
-fn part_2(locs: Vec<(Loc, Loc)>, max_grid: isize) -> isize {
-    let mut sensors: HashMap<Loc, (isize, Loc)> = HashMap::new();
-    let mut beacons: HashSet<Loc> = HashSet::new();
-    locs.into_iter().for_each(|(s, b)| {
-        sensors.insert(s, (s - b, b));
-        beacons.insert(b);
-    });
+pub struct Puzzle;
 
-    let mut x = 0;
-    let mut y = 0;
-    loop {
-        let loc = Loc(x, y);
-        let mut maybe_beacon = !sensors.contains_key(&loc) && !beacons.contains(&loc);
-
-        let jump = sensors.iter().fold(1, |acc, (s, (r, _b))| {
-            if loc - *s <= *r {
-                // too close to this sensor
-                maybe_beacon = false;
-                let dx = s.0 - loc.0;
-                let dy = s.1 - loc.1;
-                acc.max((r - dy.abs()) + dx)
-            } else {
-                acc
-            }
-        });
-        if maybe_beacon {
-            break;
-        }
-        x += jump;
+impl Solution for Puzzle {
+    const DAY: u8 = 15;
 
-        if x > max_grid {
-            x = 0;
-            y += 1;
-        }
+    type Input = Report;
+    type Answer1 = isize;
+    type Answer2 = Result<isize, NoAnswer>;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Ok(Report {
+            locs: parse(s)?,
+            row: 2_000_000,
+            max_grid: 4_000_000,
+        })
     }
 
-    dbg!(x * 4_000_000 + y)
-}
+    fn part_1(report: &Self::Input) -> isize {
+        part_1(&report.locs, report.row)
+    }
+
+    fn part_2(report: &Self::Input) -> Result<isize, NoAnswer> {
+        part_2(&report.locs, report.max_grid)
+    }
+
+    const PARAMS: &'static [&'static str] = &["row", "bound"];
 
-fn main() {
-    assert!(part_1(parse(include_str!("test.input.txt")), 10) == 26);
-    let part_1_res = part_1(parse(include_str!("input.txt")), 2_000_000);
-    println!("Part 1: {part_1_res}");
-    assert!(part_2(parse(include_str!("test.input.txt")), 20) == 56000011);
-    let part_2_res = part_2(parse(include_str!("input.txt")), 4_000_000);
-    println!("Part 2: {part_2_res}");
+    fn with_params(report: Self::Input, params: &Params) -> Result<Self::Input, ParseError> {
+        let max_grid = params.get("bound", report.max_grid)?;
+        if max_grid < 0 {
+            return Err(ParseError::new(
+                &max_grid.to_string(),
+                "a bound of 0 or more",
+            ));
+        }
+        Ok(Report {
+            row: params.get("row", report.row)?,
+            max_grid,
+            ..report
+        })
+    }
 }
@@ -0,0 +1,31 @@
+use aoc_common::{NoAnswer, Params, Solution};
+use day15::{Puzzle, Report};
+
+const EXAMPLE: &str = include_str!("../test.input.txt");
+
+fn example() -> Report {
+    let params: Params = [("row", "10"), ("bound", "20")].into_iter().collect();
+    Puzzle::with_params(Puzzle::parse(EXAMPLE).unwrap(), &params).unwrap()
+}
+
+#[test]
+fn part_1() {
+    assert_eq!(Puzzle::part_1(&example()), 26);
+}
+
+#[test]
+fn part_2() {
+    assert_eq!(Puzzle::part_2(&example()), Ok(56000011));
+}
+
+#[test]
+fn part_2_with_nowhere_left_to_look() {
+    let params: Params = [("bound", "3")].into_iter().collect();
+    let report = Puzzle::with_params(example(), &params).unwrap();
+    assert_eq!(
+        Puzzle::part_2(&report),
+        Err(NoAnswer(
+            "every spot from 0 to 3 (the bound) is in range of a sensor".to_string()
+        ))
+    );
+}
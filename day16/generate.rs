@@ -0,0 +1,67 @@
+//! Random valve networks.
+
+use std::collections::BTreeSet;
+
+use aoc_common::rng::Rng;
+
+/// About as many valves as a real input has.
+pub const SIZE: usize = 60;
+
+// the most valves the solution can keep track of
+const MOST: usize = 128;
+
+// the valves with a flow rate are what the search branches on, so keep to about as many as a
+// real input has however big the network is
+const MOST_FLOWING: usize = 15;
+
+/// A connected network of `size` valves (2 to 128), including AA, with a quarter of them (up to
+/// 15) having a flow rate.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, MOST);
+    let mut names = BTreeSet::from(["AA".to_string()]);
+    while names.len() < size {
+        let letter = |rng: &mut Rng| char::from(rng.range(b'A'..=b'Z'));
+        names.insert(format!("{}{}", letter(rng), letter(rng)));
+    }
+    let mut names: Vec<String> = names.into_iter().collect();
+    rng.shuffle(&mut names);
+
+    // a random tree joins them all up, then a few more tunnels make loops
+    let mut tunnels = vec![BTreeSet::new(); size];
+    let mut join = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for valve in 1..size {
+        join(valve, rng.range(0..valve));
+    }
+    for _ in 0..size / 4 {
+        join(rng.range(0..size), rng.range(0..size));
+    }
+
+    let mut flows = vec![0; size];
+    let flowing = (size / 4).clamp(1, MOST_FLOWING);
+    let mut valves: Vec<usize> = (0..size).filter(|&v| names[v] != "AA").collect();
+    rng.shuffle(&mut valves);
+    for &valve in &valves[..flowing] {
+        flows[valve] = rng.range(3..=25);
+    }
+
+    let mut lines: Vec<String> = (0..size)
+        .map(|valve| {
+            let to: Vec<&str> = tunnels[valve].iter().map(|&v| names[v].as_str()).collect();
+            let (name, flow) = (&names[valve], flows[valve]);
+            match to[..] {
+                [one] => format!("Valve {name} has flow rate={flow}; tunnel leads to valve {one}"),
+                _ => format!(
+                    "Valve {name} has flow rate={flow}; tunnels lead to valves {}",
+                    to.join(", ")
+                ),
+            }
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
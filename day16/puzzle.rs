@@ -1,279 +1,246 @@
-use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
-use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
-use std::iter::FromIterator;
-use std::str;
+pub mod generate;
+
+use aoc_common::intern::Interner;
+use aoc_common::scan::scan_any;
+use aoc_common::{debug, numbered_lines, search, trace, BitSet, ParseError, Solution};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Valve {
-    name: String,
+struct Valve<'a> {
+    name: &'a str,
     flow: i32,
 }
 
-impl Valve {
-    fn parse(s: &str) -> Self {
-        let name = str::from_utf8(&s.as_bytes()[6..=7])
-            .expect("string too short?")
-            .to_string();
-        let (_, flow) = s.split_once('=').expect("bad valve input");
-        let flow = flow.parse().expect("this should be an int");
-        Valve { name, flow }
+const VALVE: [&str; 2] = [
+    "Valve {} has flow rate={}; tunnels lead to valves {}",
+    "Valve {} has flow rate={}; tunnel leads to valve {}",
+];
+
+impl<'a> Valve<'a> {
+    // the valve on a line, and the names of the valves its tunnels lead to
+    fn parse(line: &'a str) -> Result<(Self, Vec<&'a str>), ParseError> {
+        let mut captures = scan_any(line, &VALVE)?;
+        let valve = Valve {
+            name: captures.text(),
+            flow: captures.parse("a flow rate")?,
+        };
+        Ok((valve, captures.text().split(", ").collect()))
     }
 }
 
-// maps of:
-//     1) valve: (flow, [connected valves])
-//     2) (valve1, valve2): cost to move from valve1 and open valve2
-// lots of allocation and cloning because lifetimes in hashmaps are hard
+// the valves are numbered in the order they're listed, and each state keeps the ones still
+// closed as a set of those numbers
+type Valves = BitSet<u128>;
+
 #[derive(Debug)]
-struct ValveMap(
-    HashMap<String, (i32, Vec<String>)>,
-    HashMap<(String, String), i32>,
-);
+pub struct ValveMap {
+    names: Interner,
+    flows: Vec<i32>,
+    // [from][to]: the minutes it takes to walk from one valve and open the other, if it's
+    // reachable at all
+    cost_to_open: Vec<Vec<Option<i32>>>,
+}
 
 impl ValveMap {
-    fn parse(s: &str) -> Self {
-        let valve_map = HashMap::from_iter(s.lines().map(|line| {
-            let (valve, tunnels) = line.split_once(';').expect("bad input");
-            let valve = Valve::parse(valve);
-            let tunnels = match tunnels.strip_prefix(" tunnels lead to valves ") {
-                None => tunnels.strip_prefix(" tunnel leads to valve ").unwrap(),
-                Some(tunnels) => tunnels,
-            };
-            let tunnels = tunnels
-                .split(", ")
-                .map(str::to_string)
-                .collect::<Vec<String>>();
-            (valve.name.clone(), (valve.flow, tunnels))
-        }));
-
-        // this is a bit gnarly but it works
-        let mut cost_to_open: HashMap<(String, String), i32> = HashMap::new();
-        for (a, (_ai, tunnels)) in valve_map.iter() {
-            // if *ai == 0 { continue; }
-            for (b, (_bi, _)) in valve_map.iter() {
-                // if *bi == 0 { continue; }
-                let mut q = VecDeque::from_iter(tunnels.iter().map(|name| (2, name)));
-                q.push_front((1, a));
-                let mut visited = HashSet::new();
-                while !q.is_empty() {
-                    let cur = q.pop_front().unwrap();
-                    if cur.1 == b {
-                        cost_to_open.insert((a.clone(), b.clone()), cur.0);
-                        break;
-                    } else {
-                        for n in valve_map.get(cur.1).unwrap().1.iter() {
-                            if visited.insert(n) {
-                                q.push_back((cur.0 + 1, n));
-                            }
-                        }
-                    }
-                }
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut names = Interner::new();
+        let mut valves = Vec::new();
+        for (n, line) in numbered_lines(s) {
+            let (valve, tunnels) = Valve::parse(line).map_err(|e| e.on_line(n))?;
+            // a second line for a valve would get its own flow and tunnels but share its number
+            if names.get(valve.name).is_some() {
+                return Err(ParseError::new(valve.name, "a valve not listed before")
+                    .at(n, line, valve.name));
+            }
+            if names.len() == Valves::CAPACITY {
+                let expected = format!("at most {} valves", Valves::CAPACITY);
+                return Err(ParseError::new(valve.name, &expected).at(n, line, valve.name));
             }
+            names.intern(valve.name);
+            valves.push((n, line, valve.flow, tunnels));
         }
 
-        ValveMap(valve_map, cost_to_open)
+        // every tunnel has to lead somewhere, and we always start at AA
+        let mut flows = Vec::new();
+        let mut tunnels = Vec::new();
+        for (n, line, flow, to) in valves {
+            flows.push(flow);
+            tunnels.push(
+                to.into_iter()
+                    .map(|name| {
+                        names.get(name).ok_or_else(|| {
+                            ParseError::new(name, "the name of a valve in the input")
+                                .at(n, line, name)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+        if names.get("AA").is_none() {
+            return Err(ParseError::new("", "a valve named AA"));
+        }
+
+        // the shortest walk between every pair of valves, plus a minute to open the far one
+        let cost_to_open = (0..names.len())
+            .map(|a| {
+                (0..names.len())
+                    .map(|b| {
+                        let search =
+                            search::bfs(a, |&valve| tunnels[valve].clone(), |&valve| valve == b);
+                        search.path.map(|path| path.cost as i32 + 1)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(ValveMap {
+            names,
+            flows,
+            cost_to_open,
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct ValveState {
     flow_so_far: i32,
     time_remaining: i32,
-    cur: String,
-    closed_valves: HashSet<String>,
-}
-
-impl Ord for ValveState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.flow_so_far.cmp(&other.flow_so_far)
-    }
+    cur: usize,
+    closed_valves: Valves,
 }
 
-impl PartialOrd for ValveState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl ValveState {
+    fn start(valve_map: &ValveMap, time_remaining: i32) -> Self {
+        ValveState {
+            flow_so_far: 0,
+            time_remaining,
+            cur: valve_map.names.get("AA").expect("parsing checks for AA"),
+            closed_valves: (0..valve_map.flows.len())
+                .filter(|&valve| valve_map.flows[valve] > 0)
+                .collect(),
+        }
     }
-}
 
-impl PartialEq for ValveState {
-    fn eq(&self, other: &Self) -> bool {
-        self.flow_so_far == other.flow_so_far
+    // the closed valves it can still get to
+    fn reachable<'a>(&self, map: &'a ValveMap) -> impl Iterator<Item = usize> + 'a {
+        let costs = &map.cost_to_open[self.cur];
+        self.closed_valves
+            .iter()
+            .filter(move |&valve| costs[valve].is_some())
     }
-}
-
-impl Eq for ValveState {}
 
-impl ValveState {
     fn upper_bound(&self, map: &ValveMap) -> i32 {
         // sum of:
         // (time_remaining - time_to_open) * flow
         // for each valve in closed_valves
         self.flow_so_far
             + self
-                .closed_valves
-                .iter()
-                .map(|name| {
-                    map.0.get(name).unwrap().0
-                        * 0.max(
-                            self.time_remaining
-                                - map.1.get(&(name.clone(), self.cur.clone())).unwrap(),
-                        )
+                .reachable(map)
+                .map(|valve| {
+                    map.flows[valve]
+                        * 0.max(self.time_remaining - map.cost_to_open[self.cur][valve].unwrap())
                 })
-                // .map(|name| map.0.get(name).unwrap().0 * (self.time_remaining - 1))
                 .sum::<i32>()
     }
-}
-
-fn part_1(valve_map: ValveMap) -> i32 {
-    let mut best = 0;
-    // initial state
-    let mut to_explore = BinaryHeap::<ValveState>::new();
-    to_explore.push(ValveState {
-        flow_so_far: 0,
-        time_remaining: 30,
-        cur: "AA".to_string(),
-        closed_valves: HashSet::from_iter(valve_map.0.iter().filter_map(|(name, (flow, _))| {
-            if *flow > 0 {
-                Some(name.clone())
-            } else {
-                None
-            }
-        })),
-    });
-    // dbg!(&to_explore);
 
-    while !to_explore.is_empty() {
-        let cur = to_explore
-            .pop()
-            .expect("queue should not be empty we just checked it");
-        best = best.max(cur.flow_so_far);
-        // naïve prune: if cur.time_remaining <= 0 { continue };
-        // smart prune
-        let current_upper_bound = cur.upper_bound(&valve_map);
-        if best >= current_upper_bound {
-            continue;
+    // move to `valve` and open it, subtracting the time it takes
+    fn open(&self, valve: usize, map: &ValveMap) -> Self {
+        let cost = map.cost_to_open[self.cur][valve].expect("only reachable valves are opened");
+        let time_remaining = 0.max(self.time_remaining - cost);
+        let mut closed_valves = self.closed_valves;
+        closed_valves.remove(valve);
+        ValveState {
+            flow_so_far: self.flow_so_far + time_remaining * map.flows[valve],
+            time_remaining,
+            cur: valve,
+            closed_valves,
         }
-
-        // for each closed valve
-        // move, subtract time, push onto to_explore
-        cur.closed_valves.iter().for_each(|valve| {
-            let time_remaining = cur.time_remaining
-                - valve_map
-                    .1
-                    .get(&(cur.cur.clone(), valve.to_string()))
-                    .unwrap();
-            let flow_so_far = cur.flow_so_far + time_remaining * valve_map.0.get(valve).unwrap().0;
-            let mut closed_valves = cur.closed_valves.clone();
-            closed_valves.remove(valve);
-            to_explore.push(ValveState {
-                flow_so_far,
-                time_remaining,
-                cur: valve.clone(),
-                closed_valves,
-            });
-        });
     }
-
-    dbg!(best)
 }
 
-fn part_2(valve_map: ValveMap) -> i32 {
-    let mut best = 0;
-    // initial state
-    let mut to_explore = BinaryHeap::<(ValveState, ValveState)>::new();
-    let closed_valves = HashSet::from_iter(valve_map.0.iter().filter_map(|(name, (flow, _))| {
-        if *flow > 0 {
-            Some(name.clone())
-        } else {
-            None
-        }
-    }));
-    to_explore.push((
-        ValveState {
-            flow_so_far: 0,
-            time_remaining: 26,
-            cur: "AA".to_string(),
-            closed_valves: closed_valves.clone(),
-        },
-        ValveState {
-            flow_so_far: 0,
-            time_remaining: 26,
-            cur: "AA".to_string(),
-            closed_valves: closed_valves.clone(),
+pub fn part_1(valve_map: &ValveMap) -> i32 {
+    let start = ValveState::start(valve_map, 30);
+    trace!("starting from {:?}", start);
+
+    // for each closed valve: move, open, and see what it's worth
+    let search = search::branch_and_bound(
+        start,
+        |cur| {
+            cur.reachable(valve_map)
+                .map(|valve| cur.open(valve, valve_map))
+                .collect::<Vec<_>>()
         },
-    ));
+        |cur| cur.flow_so_far,
+        |cur| cur.upper_bound(valve_map),
+    );
 
-    while !to_explore.is_empty() {
-        let (me, elephant) = to_explore
-            .pop()
-            .expect("queue should not be empty we just checked it");
-        best = best.max(me.flow_so_far + elephant.flow_so_far);
-        // naïve prune: if cur.time_remaining <= 0 { continue };
-        // smart prune
-        let current_upper_bound = me.upper_bound(&valve_map) + elephant.upper_bound(&valve_map);
-        if best >= current_upper_bound {
-            continue;
-        }
+    let path = search.path.expect("there's always a best state");
+    let route: Vec<&str> = path
+        .states
+        .iter()
+        .map(|s| valve_map.names.name(s.cur))
+        .collect();
+    debug!(
+        "most pressure released alone: {} via {} ({} states expanded, {} pruned)",
+        path.cost,
+        route.join(" "),
+        search.stats.expanded,
+        search.stats.pruned
+    );
+    path.cost
+}
 
-        // for each closed valve
-        // move, subtract time, push onto to_explore
-        for v1 in &me.closed_valves {
-            for v2 in &me.closed_valves {
-                if v1 == v2 {
-                    continue;
+pub fn part_2(valve_map: &ValveMap) -> i32 {
+    let start = ValveState::start(valve_map, 26);
+
+    // for each pair of closed valves: one each for me and the elephant
+    let search = search::branch_and_bound(
+        (start, start),
+        |(me, elephant)| {
+            let mut next = vec![];
+            for v1 in me.reachable(valve_map) {
+                for v2 in elephant.reachable(valve_map) {
+                    if v1 == v2 {
+                        continue;
+                    }
+                    let mut me_next = me.open(v1, valve_map);
+                    let mut elephant_next = elephant.open(v2, valve_map);
+                    me_next.closed_valves.remove(v2);
+                    elephant_next.closed_valves = me_next.closed_valves;
+                    next.push((me_next, elephant_next));
                 }
-                let mut closed_valves = me.closed_valves.clone();
-                closed_valves.remove(v1);
-                closed_valves.remove(v2);
+            }
+            next
+        },
+        |(me, elephant)| me.flow_so_far + elephant.flow_so_far,
+        |(me, elephant)| me.upper_bound(valve_map) + elephant.upper_bound(valve_map),
+    );
 
-                let me_time_remaining = 0.max(
-                    me.time_remaining - valve_map.1.get(&(me.cur.clone(), v1.to_string())).unwrap(),
-                );
-                let me_flow_so_far =
-                    me.flow_so_far + me_time_remaining * valve_map.0.get(v1).unwrap().0;
-                let me_next = ValveState {
-                    flow_so_far: me_flow_so_far,
-                    time_remaining: me_time_remaining,
-                    cur: v1.clone(),
-                    closed_valves: closed_valves.clone(),
-                };
+    let best = search.path.expect("there's always a best state").cost;
+    debug!(
+        "most pressure released with the elephant: {best} ({} states expanded, {} pruned)",
+        search.stats.expanded, search.stats.pruned
+    );
+    best
+}
 
-                let elephant_time_remaining = 0.max(
-                    elephant.time_remaining
-                        - valve_map
-                            .1
-                            .get(&(elephant.cur.clone(), v2.to_string()))
-                            .unwrap(),
-                );
-                let elephant_flow_so_far =
-                    elephant.flow_so_far + elephant_time_remaining * valve_map.0.get(v2).unwrap().0;
-                let elephant_next = ValveState {
-                    flow_so_far: elephant_flow_so_far,
-                    time_remaining: elephant_time_remaining,
-                    cur: v2.clone(),
-                    closed_valves: closed_valves.clone(),
-                };
+pub struct Puzzle;
 
-                to_explore.push((me_next, elephant_next));
-            }
-        }
-    }
+impl Solution for Puzzle {
+    const DAY: u8 = 16;
 
-    dbg!(best)
-}
+    type Input = ValveMap;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-fn main() {
-    let valve_map = ValveMap::parse(include_str!("test.input.txt"));
-    assert!(part_1(valve_map) == 1651);
-    println!(
-        "Part 1: {}",
-        part_1(ValveMap::parse(include_str!("input.txt")))
-    );
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        ValveMap::parse(s)
+    }
 
-    let valve_map = ValveMap::parse(include_str!("test.input.txt"));
-    assert!(part_2(valve_map) == 1707);
-    println!(
-        "Part 2: {}",
-        part_2(ValveMap::parse(include_str!("input.txt")))
-    );
+    fn part_1(valve_map: &Self::Input) -> i32 {
+        part_1(valve_map)
+    }
+
+    fn part_2(valve_map: &Self::Input) -> i32 {
+        part_2(valve_map)
+    }
 }
@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day16::Puzzle;
+
+const EXAMPLE: &str = include_str!("../test.input.txt");
+
+#[test]
+fn part_1() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_1(&input), 1651);
+}
+
+#[test]
+fn part_2() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_2(&input), 1707);
+}
@@ -0,0 +1,15 @@
+use aoc_common::Solution;
+use day16::Puzzle;
+
+#[test]
+fn valves_are_listed_once() {
+    let input = "\
+Valve AA has flow rate=0; tunnels lead to valves BB
+Valve BB has flow rate=13; tunnels lead to valves AA
+Valve BB has flow rate=2; tunnels lead to valves AA";
+    let error = Puzzle::parse(input).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 7: expected a valve not listed before, found \"BB\""
+    );
+}
@@ -0,0 +1,14 @@
+//! Random jet patterns.
+
+use aoc_common::rng::Rng;
+
+/// About as long as a real pattern.
+pub const SIZE: usize = 10_000;
+
+/// A pattern of `size` jets.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let jets: String = (0..size.max(1))
+        .map(|_| if rng.chance(0.5) { '<' } else { '>' })
+        .collect();
+    jets + "\n"
+}
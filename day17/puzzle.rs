@@ -1,10 +1,13 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
-use std::convert::TryInto;
-use std::iter::Cloned;
-use std::iter::Cycle;
-use std::iter::FromIterator;
+pub mod generate;
 
-#[derive(Clone, Debug)]
+use std::collections::HashSet;
+
+use aoc_common::grid::Pos;
+use aoc_common::image::{self, Image, Palette, Rgb};
+use aoc_common::render::{self, Colour, Glyph};
+use aoc_common::{cycle, debug, Grid, Params, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug)]
 enum RockShape {
     HorizontalLine,
     Plus,
@@ -20,20 +23,16 @@ struct Rock {
     ll: (usize, usize),
 }
 
-impl Rock {
-    fn rock_stream() -> Cycle<Cloned<std::slice::Iter<'static, RockShape>>> {
-        [
-            RockShape::HorizontalLine,
-            RockShape::Plus,
-            RockShape::BackwardsL,
-            RockShape::VerticalLine,
-            RockShape::Square,
-        ]
-        .iter()
-        .cloned()
-        .cycle()
-    }
+// the rocks fall in this order, over and over
+const SHAPES: [RockShape; 5] = [
+    RockShape::HorizontalLine,
+    RockShape::Plus,
+    RockShape::BackwardsL,
+    RockShape::VerticalLine,
+    RockShape::Square,
+];
 
+impl Rock {
     fn step(&mut self, jet: &char, chamber: &mut Chamber) -> bool {
         // apply jet
         match jet {
@@ -42,7 +41,7 @@ impl Rock {
             j => panic!("bad input jet {}", j),
         }
 
-        if chamber.collision(&self) {
+        if chamber.collision(self) {
             // reverse the jet
             match jet {
                 '<' => self.ll = (self.ll.0 + 1, self.ll.1),
@@ -54,11 +53,13 @@ impl Rock {
         // fall down
         self.ll = (self.ll.0, self.ll.1 - 1);
 
-        if chamber.collision(&self) {
+        if chamber.collision(self) {
             // reverse the fall
             self.ll = (self.ll.0, self.ll.1 + 1);
             // insert into chamber
-            chamber.bricks.extend(self.solid_parts().iter());
+            for part in self.solid_parts() {
+                chamber.insert(part);
+            }
             false
         } else {
             true
@@ -113,152 +114,271 @@ impl Rock {
     }
 }
 
+// air, rock, and the walls and floor
+const PALETTE: [Rgb; 3] = [Rgb::hex(0x0f0f23), Rgb::hex(0x7fb2e5), Rgb::hex(0x8c8c8c)];
+
+// how much of the top of the tower each frame of an animation shows
+const FRAME_ROWS: usize = 40;
+
 // do we really have to keep track of the entire space?
-// ah, only the bricks at and above the min of heights!
+// ah, only the rows a falling rock can still reach!
 #[derive(Debug)]
 struct Chamber {
-    bricks: HashSet<(usize, usize)>,
+    // row 0 is the lowest row still kept, `pruned` rows above the floor, and there are only as
+    // many rows as the tower is tall
+    rows: Grid<bool>,
+    pruned: usize,
+    // the pruned rows, bottom up, kept only to save a picture of the whole tower
+    out_of_reach: Option<Grid<bool>>,
 }
 
 impl Chamber {
     fn new() -> Chamber {
         Chamber {
-            bricks: HashSet::new(),
+            rows: Grid::new(7, 0, false),
+            pruned: 0,
+            out_of_reach: image::enabled().then(|| Grid::new(7, 0, false)),
         }
     }
 
-    fn _print(&self) {
-        for row in (0..=self.max()).rev() {
-            for col in 0..=7 + 1 {
-                if row == 0 {
-                    print!("-");
-                } else if col == 0 || col == 7 + 1 {
-                    print!("|");
-                } else if self.bricks.contains(&(col, row)) {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
+    // the rows still kept, top down between the walls, with the floor if it's not been pruned
+    fn draw(&self) -> String {
+        let height = self.rows.height();
+        let floor = usize::from(self.pruned == 0);
+        render::picture(7 + 2, height + floor, |(row, col)| {
+            if row == height {
+                Glyph::plain(if col == 0 || col == 7 + 1 { '+' } else { '-' })
+            } else if col == 0 || col == 7 + 1 {
+                Glyph::plain('|')
+            } else if self.rows[(height - 1 - row, col - 1)] {
+                Glyph::coloured('#', Colour::Cyan)
+            } else {
+                Glyph::coloured('.', Colour::Grey)
             }
-            println!("");
+        })
+    }
+
+    // the top `rows` rows of the tower between the walls, and the floor if it's that short
+    fn image(&self, palette: &Palette, rows: usize) -> Image {
+        let height = self.max();
+        let top = height.max(rows);
+        let floor = usize::from(top == rows);
+        Image::from_cells(7 + 2, rows + floor, palette, &PALETTE, |(row, col)| {
+            if row == rows || col == 0 || col == 7 + 1 {
+                return 2;
+            }
+            // counting up from the floor
+            let row = top - 1 - row;
+            let rock = if row >= height {
+                false
+            } else if row >= self.pruned {
+                self.rows[(row - self.pruned, col - 1)]
+            } else {
+                self.out_of_reach
+                    .as_ref()
+                    .is_some_and(|out_of_reach| out_of_reach[(row, col - 1)])
+            };
+            usize::from(rock)
+        })
+    }
+
+    // `(col, row)` of a brick, 1-based like the rocks
+    fn contains(&self, (col, row): (usize, usize)) -> bool {
+        row <= self.pruned || self.rows.get((row - self.pruned - 1, col - 1)) == Some(&true)
+    }
+
+    fn insert(&mut self, (col, row): (usize, usize)) {
+        while self.max() < row {
+            self.rows.push_row(false);
         }
+        self.rows[(row - self.pruned - 1, col - 1)] = true;
     }
 
     fn collision(&self, rock: &Rock) -> bool {
         rock.solid_parts()
             .iter()
-            .any(|x| self.bricks.contains(x) || !(1..=7).contains(&x.0) || x.1 == 0)
-    }
-
-    fn tops(&self) -> [usize; 7] {
-        (1..=7)
-            .map(|col| {
-                self.bricks
-                    .iter()
-                    .filter_map(|rock| if rock.0 == col { Some(rock.1) } else { None })
-                    .max()
-                    .unwrap_or(0)
-            })
-            .collect::<Vec<usize>>()
-            .try_into()
-            .unwrap()
+            .any(|x| !(1..=7).contains(&x.0) || x.1 == 0 || self.contains(*x))
     }
 
     fn max(&self) -> usize {
-        self.bricks
-            .iter()
-            .max_by_key(|rock| rock.1)
-            .unwrap_or(&(0, 0))
-            .1
+        self.pruned + self.rows.height()
     }
 
-    // prune below the min of heights of the columns
-    fn prune(&mut self) -> BTreeSet<(usize, usize)> {
-        let tops = self.tops();
-        let min_top = tops.iter().min().unwrap();
-        self.bricks.retain(|(_x, y)| y >= min_top);
-        BTreeSet::from_iter(self.bricks.iter().map(|(x, y)| (*x, *y - (min_top - 1))))
+    // prune the rows no rock can reach any more: a rock only moves down, left and right, so it
+    // can't get lower than the open cells reachable that way from the top row, and the row below
+    // those is the lowest it can land on
+    fn prune(&mut self) {
+        let height = self.rows.height();
+        let mut lowest = height;
+        let mut todo: Vec<Pos> = (0..7)
+            .map(|col| (height.saturating_sub(1), col))
+            .filter(|&pos| height > 0 && !self.rows[pos])
+            .collect();
+        let mut reached: HashSet<Pos> = todo.iter().copied().collect();
+        while let Some(pos) = todo.pop() {
+            lowest = lowest.min(pos.0);
+            for dir in [(-1, 0), (0, -1), (0, 1)] {
+                if let Some(next) = self.rows.step(pos, dir) {
+                    if !self.rows[next] && reached.insert(next) {
+                        todo.push(next);
+                    }
+                }
+            }
+        }
+
+        let below = lowest.saturating_sub(1);
+        if let Some(out_of_reach) = &mut self.out_of_reach {
+            for row in 0..below {
+                out_of_reach.push_row(false);
+                for col in 0..7 {
+                    let top = out_of_reach.height() - 1;
+                    out_of_reach[(top, col)] = self.rows[(row, col)];
+                }
+            }
+        }
+        self.rows.remove_rows(below);
+        self.pruned += below;
     }
 }
 
-fn part_1(s: &str, n: usize) -> usize {
-    let mut chamber = Chamber::new();
+// rocks falling into the chamber one at a time, each pushed about by the jets in turn
+struct Tower {
+    jets: Vec<char>,
+    chamber: Chamber,
+    rocks: usize,
+    // the next jet to push a rock
+    jet: usize,
+}
 
-    let mut rock_shapes = Rock::rock_stream().take(n);
-    let mut jets = s.chars().cycle();
+impl Tower {
+    fn new(jets: &str) -> Tower {
+        Tower {
+            jets: jets.chars().collect(),
+            chamber: Chamber::new(),
+            rocks: 0,
+            jet: 0,
+        }
+    }
 
-    while let Some(shape) = rock_shapes.next() {
+    fn drop_rock(&mut self) {
         let mut rock = Rock {
-            shape,
+            shape: SHAPES[self.rocks % SHAPES.len()],
             // spawn point for the rock
-            ll: (3, chamber.max() + 4),
+            ll: (3, self.chamber.max() + 4),
         };
-        while rock.step(&jets.next().unwrap(), &mut chamber) {
+        loop {
+            let jet = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            if !rock.step(&jet, &mut self.chamber) {
+                break;
+            }
         }
-        chamber.prune();
+        self.rocks += 1;
+        self.chamber.prune();
+        render::frame(&self.title(), || self.chamber.draw());
+    }
+
+    fn title(&self) -> String {
+        let pruned = self.chamber.pruned;
+        format!(
+            "{} rocks, {} high ({pruned} rows out of reach not shown)",
+            self.rocks,
+            self.height()
+        )
+    }
+
+    // everything that decides where the rest of the rocks land: the next rock and jet, and the
+    // part of the chamber they can still reach
+    fn key(&self) -> (usize, usize, Grid<bool>) {
+        (
+            self.rocks % SHAPES.len(),
+            self.jet,
+            self.chamber.rows.clone(),
+        )
+    }
+
+    fn height(&self) -> usize {
+        self.chamber.max()
     }
-    dbg!(chamber.max())
 }
 
-fn part_2(s: &str, n: usize) -> usize {
-    // hashmap is (rock_idx % 5, jet_idx, pruned top of the chamber): (rock_idx, height)
-    let mut seen: HashMap<(usize, usize, BTreeSet<(usize, usize)>), (usize, usize)> = HashMap::new();
-    let mut chamber = Chamber::new();
+pub fn part_1(s: &str, n: usize) -> usize {
+    let mut tower = Tower::new(s);
+    for _ in 0..n {
+        tower.drop_rock();
+        image::frame("day17-tower", |palette| {
+            tower.chamber.image(palette, FRAME_ROWS)
+        });
+    }
+    render::show(&tower.title(), || tower.chamber.draw());
+    image::save("day17-tower", |palette| {
+        tower.chamber.image(palette, tower.height())
+    });
+    tower.height()
+}
 
-    let mut rock_shapes = Rock::rock_stream().enumerate().take(n);
-    let mut jets = s.chars().enumerate().cycle();
+pub fn part_2(s: &str, n: usize) -> usize {
+    // far too many rocks to drop, but the tower soon starts growing the same way over and over
+    let cycle = cycle::detect(Tower::new(s), Tower::drop_rock, Tower::key, Tower::height);
+    debug!(
+        "rock {} repeats rock {}: period {}",
+        cycle.start + cycle.period,
+        cycle.start,
+        cycle.period
+    );
+    cycle.extrapolate(n)
+}
 
-    let mut period = None;
-    let mut max = 0;
-    let mut extra = 0;
+// how many rocks fall in each part, the `part_1_rocks` and `part_2_rocks` parameters
+#[derive(Debug)]
+pub struct Jets {
+    pub pattern: String,
+    pub part_1_rocks: usize,
+    pub part_2_rocks: usize,
+}
 
-    while let Some((rock_idx, shape)) = rock_shapes.next() {
-        let mut rock = Rock {
-            shape,
-            // spawn point for the rock
-            ll: (3, chamber.max() + 4),
-        };
+pub struct Puzzle;
 
-        // find the period
-        loop {
-            let (jet_idx, jet) = &jets.next().unwrap();
-            if !rock.step(jet, &mut chamber) {
-                // insert returns Some if an item is already in the HashMap
-                if let Some((seen_rock_idx, seen_max)) =
-                    seen.insert((rock_idx % 5, *jet_idx, chamber.prune()), (rock_idx, chamber.max()))
-                {
-                    period = Some(rock_idx - seen_rock_idx);
-                    let periods_needed = (n - seen_rock_idx) / period.unwrap();
-                    let height_per_period = chamber.max() - seen_max;
-                    max = seen_max + periods_needed * height_per_period;
-                    extra += (n - seen_rock_idx) % period.unwrap() - 1;  // subtract 1 because we're just over the period here
-                }
-                break;
+impl Solution for Puzzle {
+    const DAY: u8 = 17;
+
+    type Input = Jets;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let jets = s.trim();
+        if jets.is_empty() {
+            return Err(ParseError::new("", "a pattern of jets"));
+        }
+        match jets.char_indices().find(|(_, c)| *c != '<' && *c != '>') {
+            Some((i, c)) => {
+                let bad = &jets[i..i + c.len_utf8()];
+                Err(ParseError::within(jets, bad, "< or >").on_line(1))
             }
+            None => Ok(Jets {
+                pattern: jets.to_string(),
+                part_1_rocks: 2022,
+                part_2_rocks: 1_000_000_000_000,
+            }),
         }
-        if period.is_some() { break; }
     }
 
-    // run the extra few rocks
-    let max_tmp = chamber.max();
-    while let Some((_rock_idx, shape)) = rock_shapes.next() {
-        let mut rock = Rock {
-            shape,
-            ll: (3, chamber.max() + 4),
-        };
-        while rock.step(&jets.next().unwrap().1, &mut chamber) {}
-        extra -= 1;
-        if extra == 0 { break; }
+    fn part_1(jets: &Self::Input) -> usize {
+        part_1(&jets.pattern, jets.part_1_rocks)
     }
-    let max_extra = chamber.max() - max_tmp;
 
-    dbg!(max + max_extra)
-}
+    fn part_2(jets: &Self::Input) -> usize {
+        part_2(&jets.pattern, jets.part_2_rocks)
+    }
 
-fn main() {
-    assert!(part_1(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>", 2022) == 3068);
-    println!("Part 1: {}", part_1(include_str!("input.txt").trim(), 2022));
+    const PARAMS: &'static [&'static str] = &["part_1_rocks", "part_2_rocks"];
 
-    assert!(part_2(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>", 1_000_000_000_000) == 1514285714288);
-    println!("Part 2: {}", part_2(include_str!("input.txt").trim(), 1_000_000_000_000));
+    fn with_params(jets: Self::Input, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok(Jets {
+            part_1_rocks: params.get("part_1_rocks", jets.part_1_rocks)?,
+            part_2_rocks: params.get("part_2_rocks", jets.part_2_rocks)?,
+            ..jets
+        })
+    }
 }
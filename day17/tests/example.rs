@@ -0,0 +1,28 @@
+use aoc_common::Solution;
+use day17::Puzzle;
+
+const EXAMPLE: &str = include_str!("../test.input.txt");
+
+#[test]
+fn part_1() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_1(&input), 3068);
+}
+
+#[test]
+fn part_2() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_2(&input), 1514285714288);
+}
+
+#[test]
+fn part_2_agrees_with_dropping_every_rock() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    for n in (0..300).step_by(7).chain([2022]) {
+        assert_eq!(
+            day17::part_2(&input.pattern, n),
+            day17::part_1(&input.pattern, n),
+            "after {n} rocks"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//! Random lava droplets.
+
+use std::collections::HashSet;
+
+use aoc_common::rng::Rng;
+
+/// About as many cubes as a real droplet has.
+pub const SIZE: usize = 2800;
+
+/// A lumpy droplet of `size` cubes, grown out from the middle of a box 0 to 21 wide (or wider
+/// if it won't fit), so it ends up with some air pockets inside.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let width = 22.max((size as f64).cbrt() as i32 * 2);
+    let middle = width / 2;
+    let mut cubes = vec![(middle, middle, middle)];
+    let mut seen = HashSet::from([cubes[0]]);
+    while cubes.len() < size {
+        // stick a new cube onto a random side of one already there
+        let (x, y, z) = *rng.pick(&cubes);
+        let d = if rng.chance(0.5) { 1 } else { -1 };
+        let cube = match rng.range(0..3u8) {
+            0 => (x + d, y, z),
+            1 => (x, y + d, z),
+            _ => (x, y, z + d),
+        };
+        let inside = |c: i32| (0..width).contains(&c);
+        if inside(cube.0) && inside(cube.1) && inside(cube.2) && seen.insert(cube) {
+            cubes.push(cube);
+        }
+    }
+    rng.shuffle(&mut cubes);
+    cubes
+        .iter()
+        .map(|(x, y, z)| format!("{x},{y},{z}\n"))
+        .collect()
+}
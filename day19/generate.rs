@@ -0,0 +1,24 @@
+//! Random blueprint lists.
+
+use aoc_common::rng::Rng;
+
+/// As many blueprints as a real input has.
+pub const SIZE: usize = 30;
+
+/// `size` blueprints, with costs in the same ranges as a real input's.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|id| {
+            let mut ore = || rng.range(2..=4u32);
+            let (ore_bot, clay_bot, obsidian_ore, geode_ore) = (ore(), ore(), ore(), ore());
+            format!(
+                "Blueprint {id}: Each ore robot costs {ore_bot} ore. \
+                 Each clay robot costs {clay_bot} ore. \
+                 Each obsidian robot costs {obsidian_ore} ore and {} clay. \
+                 Each geode robot costs {geode_ore} ore and {} obsidian.\n",
+                rng.range(4..=20u32),
+                rng.range(6..=20u32),
+            )
+        })
+        .collect()
+}
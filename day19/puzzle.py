@@ -1,11 +1,12 @@
 import copy
 import itertools
+import math
+import re
+import sys
 from collections import defaultdict, deque
 from enum import auto, IntEnum
 from dataclasses import dataclass, field
 
-import pytest
-
 
 class Resource(IntEnum):
     GEODE = auto()
@@ -115,6 +116,30 @@ def get_max(state: State):
     return best
 
 
+def parse(fname: str) -> list[RobotBlueprint]:
+    with open(0 if fname == "-" else fname) as f:
+        blueprints = []
+        for line in f:
+            if line.strip():
+                _, ore, clay, obs_ore, obs_clay, geo_ore, geo_obs = map(
+                    int, re.findall(r"\d+", line)
+                )
+                blueprints.append(
+                    RobotBlueprint(ore, clay, (obs_ore, obs_clay), (geo_ore, geo_obs))
+                )
+    return blueprints
+
+
+def part_1(blueprints: list[RobotBlueprint]) -> int:
+    return sum(
+        i * get_max(State(b, time_remaining=24)) for i, b in enumerate(blueprints, 1)
+    )
+
+
+def part_2(blueprints: list[RobotBlueprint]) -> int:
+    return math.prod(get_max(State(b, time_remaining=32)) for b in blueprints[:3])
+
+
 def test_part_1():
     b = RobotBlueprint(4, 2, (3, 14), (2, 7))
     s = State(b, time_remaining=24)
@@ -123,3 +148,10 @@ def test_part_1():
     b = RobotBlueprint(2, 3, (3, 8), (3, 12))
     s = State(b, time_remaining=24)
     assert get_max(s) == 12
+
+
+if __name__ == "__main__":
+    # the input is the file named on the command line (- for stdin), or input.txt
+    blueprints = parse(sys.argv[1] if len(sys.argv) > 1 else "input.txt")
+    print("Part 1:", part_1(blueprints))
+    print("Part 2:", part_2(blueprints))
@@ -1,4 +1,7 @@
-use std::collections::{HashSet, VecDeque};
+pub mod generate;
+
+use aoc_common::scan::scan;
+use aoc_common::{debug, numbered_lines, search, Params, ParseError, Solution};
 
 #[derive(Debug, Eq, PartialEq)]
 enum Resource {
@@ -8,7 +11,7 @@ enum Resource {
     Geode,
 }
 
-struct Blueprint {
+pub struct Blueprint {
     ore_bot_cost: usize,
     clay_bot_cost: usize,
     obsidian_bot_cost: (usize, usize),
@@ -101,31 +104,122 @@ impl State {
             }
         }
     }
+}
 
-    fn has_excess_capacity(&self, b: &Blueprint) -> bool {
-        self.ore_bots
-            > b.ore_bot_cost
-                .max(b.clay_bot_cost)
-                .max(b.obsidian_bot_cost.0)
-                .max(b.geode_bot_cost.0)
-            || self.clay_bots > b.obsidian_bot_cost.1
-            || self.obsidian_bots > b.geode_bot_cost.1
-    }
+const BLUEPRINT: &str = "Blueprint {}: Each ore robot costs {} ore. \
+    Each clay robot costs {} ore. \
+    Each obsidian robot costs {} ore and {} clay. \
+    Each geode robot costs {} ore and {} obsidian.";
+
+fn parse_blueprint(line: &str) -> Result<Blueprint, ParseError> {
+    let mut captures = scan(line, BLUEPRINT)?;
+    captures.parse::<usize>("a blueprint number")?;
+    let mut cost = || captures.parse("a cost");
+    Ok(Blueprint {
+        ore_bot_cost: cost()?,
+        clay_bot_cost: cost()?,
+        obsidian_bot_cost: (cost()?, cost()?),
+        geode_bot_cost: (cost()?, cost()?),
+    })
 }
 
-fn parse(s: &str) -> Vec<Blueprint> {
-    s.lines()
-        .map(|line| line.split(' ').collect::<Vec<_>>())
-        .map(|words| Blueprint {
-            ore_bot_cost: words[6].parse().unwrap(),
-            clay_bot_cost: words[12].parse().unwrap(),
-            obsidian_bot_cost: (words[18].parse().unwrap(), words[21].parse().unwrap()),
-            geode_bot_cost: (words[27].parse().unwrap(), words[30].parse().unwrap()),
-        })
+pub fn parse(s: &str) -> Result<Vec<Blueprint>, ParseError> {
+    numbered_lines(s)
+        .map(|(n, line)| parse_blueprint(line).map_err(|e| e.on_line(n)))
         .collect()
 }
 
-fn get_max(b: &Blueprint, t: usize) -> usize {
+impl State {
+    // every state one minute on: buy a bot we can afford (and could still use), or just wait
+    fn successors(&self, b: &Blueprint, caps: &Caps) -> Vec<State> {
+        if self.time_remaining == 0 {
+            return vec![];
+        }
+
+        let mut next = vec![];
+        for resource in [
+            Resource::Geode,
+            Resource::Obsidian,
+            Resource::Clay,
+            Resource::Ore,
+        ] {
+            if let Some(new_state) = self.buy_bot(&resource, b) {
+                next.push(new_state);
+            }
+        }
+        next.push(State {
+            time_remaining: self.time_remaining - 1,
+            ore: self.ore + self.ore_bots,
+            clay: self.clay + self.clay_bots,
+            obsidian: self.obsidian + self.obsidian_bots,
+            geode: self.geode + self.geode_bots,
+            ..*self
+        });
+
+        next.into_iter()
+            .filter(|s| {
+                s.ore_bots <= caps.ore
+                    && s.clay_bots <= caps.clay
+                    && s.obsidian_bots <= caps.obsidian
+            })
+            .map(|s| s.canonical(caps))
+            .collect()
+    }
+
+    // treat any states that differ only in resources they can never spend as the same
+    // this is a huge (key) optimization
+    fn canonical(self, caps: &Caps) -> State {
+        // at most one bot is bought a minute, so no more than `cap` of a resource is spent a
+        // minute, and anything over what the rest of the time could ever spend is wasted
+        let t = self.time_remaining;
+        let most = |cap: usize, bots: usize| (cap * t).saturating_sub(bots * t.saturating_sub(1));
+        State {
+            ore: self.ore.min(most(caps.ore, self.ore_bots)),
+            clay: self.clay.min(most(caps.clay, self.clay_bots)),
+            obsidian: self.obsidian.min(most(caps.obsidian, self.obsidian_bots)),
+            ..self
+        }
+    }
+
+    // the geodes this state will have opened when time's up, even if it only waits
+    fn geodes_by_the_end(&self) -> usize {
+        self.geode + self.geode_bots * self.time_remaining
+    }
+
+    // ...and the most it could open if ore were free and it could buy one of every other bot
+    // each minute
+    fn most_geodes(&self, b: &Blueprint) -> usize {
+        let (mut clay, mut obsidian, mut geode) = (self.clay, self.obsidian, self.geode);
+        let (mut clay_bots, mut obsidian_bots, mut geode_bots) =
+            (self.clay_bots, self.obsidian_bots, self.geode_bots);
+        for _ in 0..self.time_remaining {
+            let buy_geode_bot = obsidian >= b.geode_bot_cost.1;
+            let buy_obsidian_bot = clay >= b.obsidian_bot_cost.1;
+            clay += clay_bots;
+            obsidian += obsidian_bots;
+            geode += geode_bots;
+            if buy_geode_bot {
+                obsidian -= b.geode_bot_cost.1;
+                geode_bots += 1;
+            }
+            if buy_obsidian_bot {
+                clay -= b.obsidian_bot_cost.1;
+                obsidian_bots += 1;
+            }
+            clay_bots += 1;
+        }
+        geode
+    }
+}
+
+// the most of each resource that can be spent in a minute, so more bots than that are no use
+struct Caps {
+    ore: usize,
+    clay: usize,
+    obsidian: usize,
+}
+
+pub fn get_max(b: &Blueprint, t: usize) -> usize {
     let init_state = State {
         time_remaining: t,
         ore: 0,
@@ -138,83 +232,89 @@ fn get_max(b: &Blueprint, t: usize) -> usize {
         geode_bots: 0,
     };
 
-    let max_ore = b.ore_bot_cost
-                .max(b.clay_bot_cost)
-                .max(b.obsidian_bot_cost.0)
-                .max(b.geode_bot_cost.0);
-    let max_clay = b.obsidian_bot_cost.1;
-    let max_obsidian = b.geode_bot_cost.1;
-
-    let mut best = 0;
-    let mut seen = HashSet::new();
-    let mut todo = VecDeque::new();
-    todo.push_back(init_state);
-    while !todo.is_empty() {
-        let cur = todo.pop_front().expect("just checked");
-
-        // treat any state with excess capacity or resources as the same
-        // this is a huge (key) optimization
-        if !seen.insert(State {
-            time_remaining: cur.time_remaining, 
-            ore: cur.ore.min(max_ore),
-            clay: cur.clay.min(max_clay),
-            obsidian: cur.obsidian.min(max_obsidian),
-            geode: cur.geode,
-            ore_bots: cur.ore_bots.min(max_ore),
-            clay_bots: cur.clay_bots.min(max_clay),
-            obsidian_bots: cur.obsidian_bots.min(max_obsidian),
-            geode_bots: cur.geode_bots,
-        }) {
-            continue;
-        }
-
-        best = best.max(cur.geode);
-
-        if cur.time_remaining == 0 {
-            continue;
-        }
+    let caps = Caps {
+        ore: b
+            .ore_bot_cost
+            .max(b.clay_bot_cost)
+            .max(b.obsidian_bot_cost.0)
+            .max(b.geode_bot_cost.0),
+        clay: b.obsidian_bot_cost.1,
+        obsidian: b.geode_bot_cost.1,
+    };
 
-        for resource in [
-            Resource::Geode,
-            Resource::Obsidian,
-            Resource::Clay,
-            Resource::Ore,
-        ] {
-            if let Some(new_state) = cur.buy_bot(&resource, &b) {
-                todo.push_back(new_state);
-            }
-        }
+    let search = search::branch_and_bound(
+        init_state,
+        |cur| cur.successors(b, &caps),
+        State::geodes_by_the_end,
+        |cur| cur.most_geodes(b),
+    );
+    let best = search.path.expect("there's always a best state").cost;
 
-        todo.push_back(State {
-            time_remaining: cur.time_remaining - 1,
-            ore: cur.ore + cur.ore_bots,
-            clay: cur.clay + cur.clay_bots,
-            obsidian: cur.obsidian + cur.obsidian_bots,
-            geode: cur.geode + cur.geode_bots,
-            ore_bots: cur.ore_bots,
-            clay_bots: cur.clay_bots,
-            obsidian_bots: cur.obsidian_bots,
-            geode_bots: cur.geode_bots,
-        });
-    }
+    debug!(
+        "blueprint can open {best} geodes in {t} minutes ({} states expanded, {} pruned)",
+        search.stats.expanded, search.stats.pruned
+    );
+    best
+}
 
-    dbg!(best)
+pub fn part_1(blueprints: &[Blueprint], minutes: usize) -> usize {
+    blueprints
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            debug!("blueprint {}", i + 1);
+            (i + 1) * get_max(b, minutes)
+        })
+        .sum()
 }
 
-fn part_1(blueprints: Vec<Blueprint>) -> usize {
-    blueprints.iter().enumerate().map(|(i, b)| dbg!(i + 1) * get_max(b, 24)).sum()
+pub fn part_2(blueprints: &[Blueprint], minutes: usize) -> usize {
+    blueprints
+        .iter()
+        .take(3)
+        .map(|b| get_max(b, minutes))
+        .product()
 }
 
-fn part_2(blueprints: Vec<Blueprint>) -> usize {
-    blueprints.iter().take(3).map(|b| get_max(b, 32)).product()
+// how long each part runs for, the `part_1_minutes` and `part_2_minutes` parameters
+pub struct Blueprints {
+    pub blueprints: Vec<Blueprint>,
+    pub part_1_minutes: usize,
+    pub part_2_minutes: usize,
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 19;
+
+    type Input = Blueprints;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Ok(Blueprints {
+            blueprints: parse(s)?,
+            part_1_minutes: 24,
+            part_2_minutes: 32,
+        })
+    }
+
+    fn part_1(input: &Self::Input) -> usize {
+        part_1(&input.blueprints, input.part_1_minutes)
+    }
 
-fn main() {
-    assert_eq!(part_1(parse(include_str!("test.input.txt"))), 33);
-    println!("Part 1: {}", part_1(parse(include_str!("input.txt"))));
+    fn part_2(input: &Self::Input) -> usize {
+        part_2(&input.blueprints, input.part_2_minutes)
+    }
+
+    const PARAMS: &'static [&'static str] = &["part_1_minutes", "part_2_minutes"];
 
-    assert_eq!(get_max(&parse(include_str!("test.input.txt"))[0], 32), 56);
-    assert_eq!(get_max(&parse(include_str!("test.input.txt"))[1], 32), 62);
-    println!("Part 2: {}", part_2(parse(include_str!("input.txt"))));
+    fn with_params(input: Self::Input, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok(Blueprints {
+            part_1_minutes: params.get("part_1_minutes", input.part_1_minutes)?,
+            part_2_minutes: params.get("part_2_minutes", input.part_2_minutes)?,
+            ..input
+        })
+    }
 }
@@ -0,0 +1,18 @@
+use aoc_common::Solution;
+use day19::Puzzle;
+
+const EXAMPLE: &str = include_str!("../test.input.txt");
+
+#[test]
+fn part_1() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_1(&input), 33);
+}
+
+#[test]
+fn part_2() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(day19::get_max(&input.blueprints[0], 32), 56);
+    assert_eq!(day19::get_max(&input.blueprints[1], 32), 62);
+    assert_eq!(Puzzle::part_2(&input), 3472);
+}
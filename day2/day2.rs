@@ -1,57 +1,47 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::str::FromStr;
+pub mod generate;
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+use std::str::FromStr;
 
-#[derive(Copy, Clone, Debug)]
-struct ParseRPSError;
+use aoc_common::{numbered_lines, ParseError, Solution};
 
 #[derive(Copy, Clone, Debug)]
 #[repr(u8)]
-enum RPSOutcome {
+pub enum RPSOutcome {
     Win = 6,
     Loss = 0,
     Draw = 3,
 }
 
 impl FromStr for RPSOutcome {
-    type Err = ParseRPSError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "X" => Ok(RPSOutcome::Loss),
             "Y" => Ok(RPSOutcome::Draw),
             "Z" => Ok(RPSOutcome::Win),
-            _ => Err(ParseRPSError),
+            _ => Err(ParseError::new(s, "X, Y or Z")),
         }
     }
 }
 
 #[derive(Copy, Clone, Debug)]
 #[repr(u8)]
-enum RPSMove {
+pub enum RPSMove {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
 }
 
 impl FromStr for RPSMove {
-    type Err = ParseRPSError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "A" | "X" => Ok(RPSMove::Rock),
             "B" | "Y" => Ok(RPSMove::Paper),
             "C" | "Z" => Ok(RPSMove::Scissors),
-            _ => Err(ParseRPSError),
+            _ => Err(ParseError::new(s, "A, B, C, X, Y or Z")),
         }
     }
 }
@@ -81,40 +71,43 @@ fn get_move(them: RPSMove, outcome: RPSOutcome) -> RPSMove {
     }
 }
 
-fn main() {
-    // part 1
-    let scores = read_lines("./input.txt").unwrap().map(|line| {
-        let line = line.unwrap();
-        let mut guide = line.split_whitespace();
-        let them: RPSMove = guide
-            .next()
-            .expect("no first move")
-            .parse()
-            .expect("invalid first move");
-        let us: RPSMove = guide
-            .next()
-            .expect("no second move")
-            .parse()
-            .expect("invalid second move");
-        get_score(them, us) as u32
-    });
-    println!("Part 1: {}", scores.sum::<u32>());
-
-    // part 2
-    let scores = read_lines("./input.txt").unwrap().map(|line| {
-        let line = line.unwrap();
-        let mut guide = line.split_whitespace();
-        let them: RPSMove = guide
-            .next()
-            .expect("no first move")
-            .parse()
-            .expect("invalid first move");
-        let outcome: RPSOutcome = guide
-            .next()
-            .expect("no outcome")
-            .parse()
-            .expect("invalid outcome");
-        get_score(them, get_move(them, outcome)) as u32
-    });
-    println!("Part 2: {}", scores.sum::<u32>());
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 2;
+
+    // the second column of the strategy guide is either our move (part 1) or the outcome (part 2)
+    type Input = Vec<(RPSMove, RPSMove, RPSOutcome)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        numbered_lines(s)
+            .map(|(n, line)| {
+                let (them, us) = line
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::new(line, "<A|B|C> <X|Y|Z>").on_line(n))?;
+                let them: RPSMove = them.parse().map_err(|e: ParseError| e.at(n, line, them))?;
+                let (us, outcome) = match (us.parse::<RPSMove>(), us.parse::<RPSOutcome>()) {
+                    (Ok(us), Ok(outcome)) => (us, outcome),
+                    _ => return Err(ParseError::new(us, "X, Y or Z").at(n, line, us)),
+                };
+                Ok((them, us, outcome))
+            })
+            .collect()
+    }
+
+    fn part_1(guide: &Self::Input) -> u32 {
+        guide
+            .iter()
+            .map(|&(them, us, _)| get_score(them, us) as u32)
+            .sum::<u32>()
+    }
+
+    fn part_2(guide: &Self::Input) -> u32 {
+        guide
+            .iter()
+            .map(|&(them, _, outcome)| get_score(them, get_move(them, outcome)) as u32)
+            .sum::<u32>()
+    }
 }
@@ -0,0 +1,17 @@
+//! Random strategy guides.
+
+use aoc_common::rng::Rng;
+
+/// About as many rounds as a real input has.
+pub const SIZE: usize = 2500;
+
+/// A strategy guide for `size` rounds.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let them = rng.pick(&['A', 'B', 'C']);
+            let us = rng.pick(&['X', 'Y', 'Z']);
+            format!("{them} {us}\n")
+        })
+        .collect()
+}
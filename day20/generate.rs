@@ -0,0 +1,26 @@
+//! Random encrypted files.
+
+use aoc_common::rng::Rng;
+
+/// About as many numbers as a real file has.
+pub const SIZE: usize = 5000;
+
+/// A file of `size` numbers, exactly one of them 0, since that's where the coordinates are
+/// counted from.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let zero = rng.range(0..size);
+    (0..size)
+        .map(|i| {
+            if i == zero {
+                return "0\n".to_string();
+            }
+            loop {
+                let n = rng.range(-10_000..=10_000i64);
+                if n != 0 {
+                    return format!("{n}\n");
+                }
+            }
+        })
+        .collect()
+}
@@ -1,23 +1,42 @@
+pub mod generate;
+
 use std::collections::HashMap;
 use std::iter::FromIterator;
 
-fn parse(s: &str) -> (Vec<isize>, HashMap<usize, (usize, usize)>) {
-    let nums: Vec<isize> = s.lines().map(|s| s.parse().unwrap()).collect();
+use aoc_common::{debug, numbered_lines, ParseError, Solution};
+
+// each index's (previous, next) neighbours in the circular list
+type Links = HashMap<usize, (usize, usize)>;
+
+pub fn parse(s: &str) -> Result<(Vec<isize>, Links), ParseError> {
+    let nums: Vec<isize> = numbered_lines(s)
+        .map(|(n, s)| {
+            s.parse()
+                .map_err(|_| ParseError::new(s, "an integer").on_line(n))
+        })
+        .collect::<Result<_, _>>()?;
+    // mixing needs somewhere to move to, and the coordinates are counted from 0
+    if nums.len() < 2 || !nums.contains(&0) {
+        return Err(ParseError::new(
+            s,
+            "a list of at least two numbers, including 0",
+        ));
+    }
     let idx: Vec<usize> = (0..nums.len()).collect();
     let mut prev: Vec<usize> = idx.clone();
     prev.rotate_right(1);
     let mut next: Vec<usize> = idx.clone();
     next.rotate_left(1);
 
-    (
+    Ok((
         nums,
-        HashMap::from_iter(idx.into_iter().zip(prev.into_iter().zip(next.into_iter()))),
-    )
+        HashMap::from_iter(idx.into_iter().zip(prev.into_iter().zip(next))),
+    ))
 }
 
 #[derive(Debug)]
 struct LL {
-    map: HashMap<usize, (usize, usize)>,
+    map: Links,
     len: usize,
 }
 
@@ -56,7 +75,23 @@ impl LL {
     }
 }
 
-fn part_1(nums: &Vec<isize>, map: HashMap<usize, (usize, usize)>) -> isize {
+// the sum of the numbers 1000, 2000 and 3000 places after the 0
+fn grove_coordinates(nums: &[isize], list: &LL) -> isize {
+    let index_of_zero = nums.iter().position(|&x| x == 0).unwrap();
+    debug!("0 is at index {index_of_zero}");
+    [1000, 2000, 3000]
+        .into_iter()
+        .map(|steps| {
+            let n = nums[(0..steps).fold(index_of_zero, |acc, _v| {
+                list.map.get(&acc).expect("can't chase fwd").1
+            })];
+            debug!("{steps} after 0: {n}");
+            n
+        })
+        .sum()
+}
+
+pub fn part_1(nums: &[isize], map: Links) -> isize {
     let mut list = LL {
         map,
         len: nums.len(),
@@ -65,29 +100,10 @@ fn part_1(nums: &Vec<isize>, map: HashMap<usize, (usize, usize)>) -> isize {
         list.shift(i, *n);
     });
 
-    let index_of_zero = nums.iter().position(|&x| x == 0).unwrap();
-    dbg!(
-        nums[(0..1000).fold(dbg!(index_of_zero), |acc, _v| list
-            .map
-            .get(&acc)
-            .expect("can't chase fwd")
-            .1)]
-    ) + dbg!(
-        nums[(0..2000).fold(index_of_zero, |acc, _v| list
-            .map
-            .get(&acc)
-            .expect("can't chase fwd")
-            .1)]
-    ) + dbg!(
-        nums[(0..3000).fold(index_of_zero, |acc, _v| list
-            .map
-            .get(&acc)
-            .expect("can't chase fwd")
-            .1)]
-    )
+    grove_coordinates(nums, &list)
 }
 
-fn part_2(nums: &Vec<isize>, map: HashMap<usize, (usize, usize)>) -> isize {
+pub fn part_2(nums: &[isize], map: Links) -> isize {
     let mut list = LL {
         map,
         len: nums.len(),
@@ -96,49 +112,33 @@ fn part_2(nums: &Vec<isize>, map: HashMap<usize, (usize, usize)>) -> isize {
     let nums = nums.iter().map(|n| n * 811589153).collect::<Vec<isize>>();
 
     for i in 0..10 {
-        println!("Mixing {i}");
+        debug!("mixing round {}", i + 1);
         nums.iter().enumerate().for_each(|(i, n)| {
             list.shift(i, *n);
         });
     }
 
-    let index_of_zero = nums.iter().position(|&x| x == 0).unwrap();
-    dbg!(
-        nums[(0..1000).fold(dbg!(index_of_zero), |acc, _v| list
-            .map
-            .get(&acc)
-            .expect("can't chase fwd")
-            .1)]
-    ) + dbg!(
-        nums[(0..2000).fold(index_of_zero, |acc, _v| list
-            .map
-            .get(&acc)
-            .expect("can't chase fwd")
-            .1)]
-    ) + dbg!(
-        nums[(0..3000).fold(index_of_zero, |acc, _v| list
-            .map
-            .get(&acc)
-            .expect("can't chase fwd")
-            .1)]
-    )
+    grove_coordinates(&nums, &list)
 }
 
-fn main() {
-    // dbg!(parse(include_str!("test.input.txt")));
-    let (nums, map) = parse(include_str!("test.input.txt"));
-    assert_eq!(part_1(&nums, map), 3);
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 20;
+
+    type Input = (Vec<isize>, Links);
+    type Answer1 = isize;
+    type Answer2 = isize;
 
-    let (nums, map) = parse(include_str!("input.txt"));
-    let part_1_res = part_1(&nums, map);
-    println!("Part 1 {}", part_1_res);
-    assert_eq!(part_1_res, 7153);
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
 
-    let (nums, map) = parse(include_str!("test.input.txt"));
-    assert_eq!(part_2(&nums, map), 1623178306);
+    fn part_1((nums, map): &Self::Input) -> isize {
+        part_1(nums, map.clone())
+    }
 
-    let (nums, map) = parse(include_str!("input.txt"));
-    let part_2_res = part_2(&nums, map);
-    println!("Part 2 {}", part_2_res);
-    assert_eq!(part_2_res, 6146976244822);
+    fn part_2((nums, map): &Self::Input) -> isize {
+        part_2(nums, map.clone())
+    }
 }
@@ -0,0 +1,82 @@
+//! Random monkey riddles.
+
+use std::collections::HashSet;
+
+use aoc_common::rng::Rng;
+
+/// About as many monkeys as a real input has.
+pub const SIZE: usize = 2200;
+
+struct Riddle<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    jobs: Vec<String>,
+}
+
+// products beyond this get added instead, so nothing overflows
+const LARGEST: i64 = 1 << 40;
+
+impl Riddle<'_> {
+    // the name for a monkey depending on `leaves` others, which is the human if it's just them
+    fn child(&mut self, leaves: usize, humn_at: Option<usize>) -> String {
+        match (leaves, humn_at) {
+            (1, Some(_)) => "humn".to_string(),
+            _ => self.name(),
+        }
+    }
+
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| char::from(self.rng.range(b'a'..=b'z')))
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    // a monkey called `name` whose number depends on `leaves` others that just yell one, the
+    // human being the one `humn_at` from the left if it's among them; returns what it yells
+    fn monkey(&mut self, name: &str, leaves: usize, humn_at: Option<usize>) -> i64 {
+        if leaves == 1 {
+            let n = self.rng.range(1..=20i64);
+            self.jobs.push(format!("{name}: {n}"));
+            return n;
+        }
+        let left = self.rng.range(1..leaves);
+        let humn_left = humn_at.filter(|&h| h < left);
+        let humn_right = humn_at.and_then(|h| h.checked_sub(left));
+        let l = self.child(left, humn_left);
+        let r = self.child(leaves - left, humn_right);
+        let a = self.monkey(&l, left, humn_left);
+        let b = self.monkey(&r, leaves - left, humn_right);
+
+        // division has to come out exactly
+        let (op, n) = match self.rng.range(0..4u8) {
+            0 if b != 0 && a % b == 0 => ('/', a / b),
+            1 if a.checked_mul(b).is_some_and(|n| n.abs() < LARGEST) => ('*', a * b),
+            2 => ('-', a - b),
+            _ => ('+', a + b),
+        };
+        self.jobs.push(format!("{name}: {l} {op} {r}"));
+        n
+    }
+}
+
+/// A riddle for `size` monkeys (at least 3), one of them the human, where every monkey but root
+/// is needed by exactly one other and every division comes out exactly.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // every monkey waiting on others waits on two, so the tree has about half as many leaves
+    let leaves = size.max(3).div_ceil(2);
+    let humn_at = rng.range(0..leaves);
+    let mut riddle = Riddle {
+        rng,
+        names: HashSet::new(),
+        jobs: vec![],
+    };
+    riddle.monkey("root", leaves, Some(humn_at));
+    let mut jobs = riddle.jobs;
+    rng.shuffle(&mut jobs);
+    jobs.join("\n") + "\n"
+}
@@ -1,7 +1,9 @@
+import sys
+
 from z3 import Int, Optimize, sat
 
 def parse(fname: str) -> dict[str, int | tuple[str, str, str]]:
-    with open(fname) as f:
+    with open(0 if fname == "-" else fname) as f:
         monkeys = f.readlines()
 
     constraints: dict[str, int | tuple[str, str, str]] = {}
@@ -47,5 +49,6 @@ def test_part_2():
     assert part_2(parse("test.input.txt")) == 301
 
 if __name__ == "__main__":
-    constraints = parse("input.txt")
+    # the input is the file named on the command line (- for stdin), or input.txt
+    constraints = parse(sys.argv[1] if len(sys.argv) > 1 else "input.txt")
     print("Part 2:", part_2(constraints))
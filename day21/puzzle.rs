@@ -1,9 +1,13 @@
+pub mod generate;
+
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::iter::FromIterator;
+use std::fmt;
 use std::str::FromStr;
 
+use aoc_common::{debug, numbered_lines, ParseError, Solution, Unsolved};
+
 #[derive(Copy, Clone, Debug)]
-enum Op {
+pub enum Op {
     Add,
     Sub,
     Div,
@@ -22,7 +26,7 @@ impl Op {
 }
 
 impl FromStr for Op {
-    type Err = ParseMonkeyError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -30,44 +34,111 @@ impl FromStr for Op {
             "-" => Ok(Op::Sub),
             "/" => Ok(Op::Div),
             "*" => Ok(Op::Mul),
-            _ => Err(ParseMonkeyError),
+            _ => Err(ParseError::new(s, "+, -, * or /")),
         }
     }
 }
 
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Div => "/",
+            Op::Mul => "*",
+        };
+        f.write_str(op)
+    }
+}
+
 #[derive(Clone, Debug)]
-enum Monkey {
+pub enum Monkey {
     Done(isize),
     Pending(String, String, Op),
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseMonkeyError;
-
 impl FromStr for Monkey {
-    type Err = ParseMonkeyError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(num) = s.parse::<isize>() {
             Ok(Monkey::Done(num))
         } else {
-            let mut tokens = s.split(' ');
-            let m1 = tokens.next().unwrap().to_string();
-            let op = tokens.next().unwrap().parse().unwrap();
-            let m2 = tokens.next().unwrap().to_string();
-            Ok(Monkey::Pending(m1, m2, op))
+            let tokens = s.split(' ').collect::<Vec<_>>();
+            let [m1, op, m2] = tokens[..] else {
+                return Err(ParseError::new(s, "a number or <monkey> <op> <monkey>"));
+            };
+            let op = op
+                .parse()
+                .map_err(|_| ParseError::within(s, op, "+, -, * or /"))?;
+            Ok(Monkey::Pending(m1.to_string(), m2.to_string(), op))
+        }
+    }
+}
+
+impl fmt::Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Monkey::Done(num) => write!(f, "{num}"),
+            Monkey::Pending(m1, m2, op) => write!(f, "{m1} {op} {m2}"),
         }
     }
 }
 
-fn parse(s: &str) -> HashMap<String, Monkey> {
-    HashMap::from_iter(s.lines().map(|line| {
-        let (monkey, op) = line.split_once(": ").unwrap();
-        (monkey.to_string(), op.parse::<Monkey>().unwrap())
-    }))
+/// A line of the input: a monkey's name and its job.
+#[derive(Clone, Debug)]
+pub struct Line {
+    pub name: String,
+    pub monkey: Monkey,
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, job) = s
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(s, "<monkey>: <job>"))?;
+        Ok(Line {
+            name: name.to_string(),
+            monkey: job.parse().map_err(|e: ParseError| e.relative_to(s, job))?,
+        })
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.monkey)
+    }
+}
+
+pub fn parse(s: &str) -> Result<HashMap<String, Monkey>, ParseError> {
+    let mut jobs = HashMap::new();
+    let mut waiting = Vec::new();
+    for (n, line) in numbered_lines(s) {
+        let Line { name, monkey } = line.parse().map_err(|e: ParseError| e.on_line(n))?;
+        if let Monkey::Pending(..) = monkey {
+            waiting.push((n, line));
+        }
+        jobs.insert(name, monkey);
+    }
+
+    // every monkey has to be waiting on monkeys that exist, starting from root
+    if !jobs.contains_key("root") {
+        return Err(ParseError::new("", "a monkey named root"));
+    }
+    for (n, line) in waiting {
+        let (_, job) = line.split_once(": ").expect("the line parsed");
+        for m in job.split(' ').step_by(2) {
+            if !jobs.contains_key(m) {
+                return Err(ParseError::new(m, "the name of a monkey in the input").at(n, line, m));
+            }
+        }
+    }
+    Ok(jobs)
 }
 
-fn part_1(mut jobs: HashMap<String, Monkey>) -> isize {
+pub fn part_1(mut jobs: HashMap<String, Monkey>) -> isize {
     let mut pending = VecDeque::from(["root".to_string()]);
     let mut pending_set = HashSet::from(["root".to_string()]);
 
@@ -113,17 +184,33 @@ fn part_1(mut jobs: HashMap<String, Monkey>) -> isize {
         }
     }
 
-    if let Monkey::Done(res) = dbg!(jobs.get("root").unwrap()) {
+    let root = jobs.get("root").unwrap();
+    debug!("root: {root:?}");
+    if let Monkey::Done(res) = root {
         *res
     } else {
         0
     }
 }
 
-fn main() {
-    let jobs = parse(include_str!("test.input.txt"));
-    assert_eq!(part_1(jobs), 152);
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 21;
 
-    let jobs = parse(include_str!("input.txt"));
-    println!("Part 1 {}", part_1(jobs));
+    type Input = HashMap<String, Monkey>;
+    type Answer1 = isize;
+    type Answer2 = Unsolved;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part_1(jobs: &Self::Input) -> isize {
+        part_1(jobs.clone())
+    }
+
+    fn part_2(_jobs: &Self::Input) -> Unsolved {
+        Unsolved("day21/puzzle.py")
+    }
 }
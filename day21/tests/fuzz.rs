@@ -0,0 +1,7 @@
+use aoc_common::fuzz;
+use day21::{generate, Line, Puzzle};
+
+#[test]
+fn jobs_parse_and_round_trip() {
+    fuzz::check::<Puzzle, Line>(env!("CARGO_MANIFEST_DIR"), generate::generate);
+}
@@ -0,0 +1,75 @@
+//! Random monkey maps.
+
+use aoc_common::rng::Rng;
+
+/// The edge length of a real input's cube faces.
+pub const SIZE: usize = 50;
+
+// the eleven ways to unfold a cube, as which blocks of the map hold a face
+const NETS: [&[&str]; 11] = [
+    &["#...", "####", "#..."],
+    &["#...", "####", ".#.."],
+    &["#...", "####", "..#."],
+    &["#...", "####", "...#"],
+    &[".#..", "####", ".#.."],
+    &[".#..", "####", "..#."],
+    &["##..", ".###", ".#.."],
+    &["##..", ".###", "..#."],
+    &["##..", ".###", "...#"],
+    &["##..", ".##.", "..##"],
+    &["###..", "..###"],
+];
+
+// a net picked at random, turned and flipped at random
+fn net(rng: &mut Rng) -> Vec<Vec<bool>> {
+    let mut net: Vec<Vec<bool>> = rng
+        .pick(&NETS)
+        .iter()
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect();
+    if rng.chance(0.5) {
+        net = (0..net[0].len())
+            .map(|col| net.iter().map(|row| row[col]).collect())
+            .collect();
+    }
+    if rng.chance(0.5) {
+        net.reverse();
+    }
+    if rng.chance(0.5) {
+        net.iter_mut().for_each(|row| row.reverse());
+    }
+    net
+}
+
+/// A map that folds into a cube with faces `size` tiles across, scattered with walls, and a path
+/// of `40 * size` moves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(1);
+    let net = net(rng);
+    let mut map = String::new();
+    let mut start = true;
+    for row in 0..net.len() * n {
+        let mut line = String::new();
+        for col in 0..net[0].len() * n {
+            line.push(match net[row / n][col / n] {
+                false => ' ',
+                // the top left tile is where the path starts, so it has to be open
+                true if start => {
+                    start = false;
+                    '.'
+                }
+                true if rng.chance(0.1) => '#',
+                true => '.',
+            });
+        }
+        map += line.trim_end();
+        map.push('\n');
+    }
+
+    let mut path = rng.range(1..=50u32).to_string();
+    for _ in 1..40 * n {
+        path.push(*rng.pick(&['L', 'R']));
+        path += &rng.range(1..=50u32).to_string();
+    }
+    format!("{map}\n{path}\n")
+}
@@ -1,41 +1,77 @@
-use std::collections::HashMap;
-use std::iter::FromIterator;
-use std::ops::RangeInclusive;
+pub mod generate;
+pub mod puzzle2;
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
-struct Loc {
-    row: usize,
-    col: usize,
-}
+use aoc_common::grid::Pos;
+use aoc_common::image::{self, Image, Palette, Rgb};
+use aoc_common::render::{self, Colour, Glyph};
+use aoc_common::{debug, numbered_lines, Grid, Params, ParseError, Solution};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum Tile {
+    // off the edge of the map
+    Void,
     Open,
     Solid,
 }
 
+impl Tile {
+    fn from_char(c: char) -> Option<Tile> {
+        match c {
+            ' ' => Some(Tile::Void),
+            '.' => Some(Tile::Open),
+            '#' => Some(Tile::Solid),
+            _ => None,
+        }
+    }
+}
+
+// the map with the path taken so far drawn on it, as arrows pointing the way it went
+fn draw(tiles: &Grid<Tile>, trail: &Grid<Option<char>>) -> String {
+    render::grid(tiles, |pos, tile| match (trail[pos], tile) {
+        (Some(arrow), _) => Glyph::coloured(arrow, Colour::Yellow),
+        (None, Tile::Void) => Glyph::plain(' '),
+        (None, Tile::Open) => Glyph::coloured('.', Colour::Grey),
+        (None, Tile::Solid) => Glyph::coloured('#', Colour::White),
+    })
+}
+
+// off the map, open and solid tiles, the path so far, and where it's got to
+const PALETTE: [Rgb; 5] = [
+    Rgb::hex(0x0f0f23),
+    Rgb::hex(0x3c3c50),
+    Rgb::hex(0xb4b4b4),
+    Rgb::hex(0xffcc33),
+    Rgb::hex(0xff3333),
+];
+
+fn map_image(tiles: &Grid<Tile>, trail: &Grid<Option<char>>, at: Pos, palette: &Palette) -> Image {
+    Image::from_grid(tiles, palette, &PALETTE, |pos, tile| {
+        match (trail[pos], tile) {
+            _ if pos == at => 4,
+            (Some(_), _) => 3,
+            (None, Tile::Void) => 0,
+            (None, Tile::Open) => 1,
+            (None, Tile::Solid) => 2,
+        }
+    })
+}
+
+// the map part of the notes, padded out to a rectangle with void
+fn parse_map(map: &str) -> Result<Grid<Tile>, ParseError> {
+    Grid::parse_ragged(map, Tile::Void, Tile::from_char, "' ', '.' or '#'")
+}
+
 #[derive(Clone, Debug)]
-struct Map {
-    tiles: HashMap<Loc, Tile>,
-    row_ranges: Vec<RangeInclusive<usize>>,
-    column_ranges: Vec<RangeInclusive<usize>>,
+pub struct Map {
+    tiles: Grid<Tile>,
 }
 
 impl Map {
-    fn get_start(&self) -> Loc {
-        let col = self
-            .tiles
-            .iter()
-            .filter_map(|(k, v)| {
-                if k.row == 0 && *v == Tile::Open {
-                    Some(k.col)
-                } else {
-                    None
-                }
-            })
-            .min()
+    fn get_start(&self) -> Pos {
+        let col = (0..self.tiles.width())
+            .find(|&col| self.tiles[(0, col)] == Tile::Open)
             .unwrap();
-        Loc { row: 0, col }
+        (0, col)
     }
 }
 
@@ -47,21 +83,48 @@ enum Dir {
     Down = 1,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 struct PC<'a> {
-    loc: Loc,
+    loc: Pos,
     dir: Dir,
     map: &'a Map,
+    trail: Grid<Option<char>>,
+}
+
+impl Dir {
+    // (row, col)
+    fn step(self) -> (isize, isize) {
+        match self {
+            Dir::Right => (0, 1),
+            Dir::Up => (-1, 0),
+            Dir::Left => (0, -1),
+            Dir::Down => (1, 0),
+        }
+    }
+
+    fn arrow(self) -> char {
+        match self {
+            Dir::Right => '>',
+            Dir::Up => '^',
+            Dir::Left => '<',
+            Dir::Down => 'v',
+        }
+    }
 }
 
 impl<'a> PC<'a> {
+    fn mark(&mut self) {
+        self.trail[self.loc] = Some(self.dir.arrow());
+    }
+
     fn turn_left(&mut self) {
         self.dir = match self.dir {
             Dir::Right => Dir::Up,
             Dir::Up => Dir::Left,
             Dir::Left => Dir::Down,
             Dir::Down => Dir::Right,
-        }
+        };
+        self.mark();
     }
 
     fn turn_right(&mut self) {
@@ -70,108 +133,81 @@ impl<'a> PC<'a> {
             Dir::Down => Dir::Left,
             Dir::Left => Dir::Up,
             Dir::Up => Dir::Right,
-        }
+        };
+        self.mark();
     }
 
-    // this is terrible
     fn move_ahead(&mut self, n: usize) {
+        let step = self.dir.step();
+        let back = (-step.0, -step.1);
         for _ in 1..=n {
-            let nxt_loc = match self.dir {
-                Dir::Right => {
-                    // wrapping add around
-                    let nxt_col =
-                        if !self.map.row_ranges[self.loc.row].contains(&(self.loc.col + 1)) {
-                            *self.map.row_ranges[self.loc.row].start()
-                        } else {
-                            self.loc.col + 1
-                        };
-                    Loc {
-                        row: self.loc.row,
-                        col: nxt_col,
-                    }
-                }
-                Dir::Left => {
-                    // wrapping add around
-                    let nxt_col =
-                        if !self.map.row_ranges[self.loc.row].contains(&(self.loc.col - 1)) {
-                            *self.map.row_ranges[self.loc.row].end()
-                        } else {
-                            self.loc.col - 1
-                        };
-                    Loc {
-                        row: self.loc.row,
-                        col: nxt_col,
-                    }
-                }
-                Dir::Up => {
-                    // wrapping add around
-                    let nxt_row =
-                        if !self.map.column_ranges[self.loc.col].contains(&(self.loc.row - 1)) {
-                            *self.map.column_ranges[self.loc.col].end()
-                        } else {
-                            self.loc.row - 1
-                        };
-                    Loc {
-                        row: nxt_row,
-                        col: self.loc.col,
-                    }
-                }
-                Dir::Down => {
-                    // wrapping add around
-                    let nxt_row =
-                        if !self.map.column_ranges[self.loc.col].contains(&(self.loc.row + 1)) {
-                            *self.map.column_ranges[self.loc.col].start()
-                        } else {
-                            self.loc.row + 1
-                        };
-                    Loc {
-                        row: nxt_row,
-                        col: self.loc.col,
-                    }
-                }
+            let tiles = &self.map.tiles;
+            let nxt_loc = match tiles.step(self.loc, step) {
+                Some(loc) if tiles[loc] != Tile::Void => loc,
+                // wrap around to the far end of the row or column
+                _ => tiles
+                    .ray(self.loc, back)
+                    .take_while(|loc| tiles[*loc] != Tile::Void)
+                    .last()
+                    .unwrap_or(self.loc),
             };
-            if *self.map.tiles.get(&nxt_loc).unwrap() == Tile::Open {
-                self.loc = nxt_loc
+            if tiles[nxt_loc] == Tile::Open {
+                self.loc = nxt_loc;
+                self.mark();
             };
         }
     }
 }
 
-fn parse(s: &str) -> (Map, Vec<String>) {
-    let tiles = HashMap::from_iter(s.lines().take_while(|x| *x != "\n").enumerate().flat_map(
-        |(i, line)| {
-            line.chars().enumerate().filter_map(move |(j, c)| match c {
-                '.' => Some((Loc { row: i, col: j }, Tile::Open)),
-                '#' => Some((Loc { row: i, col: j }, Tile::Solid)),
-                _ => None,
-            })
-        },
-    ));
-
-    let rows = 1 + tiles.iter().max_by_key(|(k, _)| k.row).unwrap().0.row;
-    let cols = 1 + tiles.iter().max_by_key(|(k, _)| k.col).unwrap().0.col;
-
-    let row_ranges = (0..rows)
-        .map(|row| {
-            let cols_in_row =
-                tiles
-                    .iter()
-                    .filter_map(|(k, _)| if k.row == row { Some(k.col) } else { None });
-            cols_in_row.clone().min().unwrap()..=cols_in_row.max().unwrap()
-        })
-        .collect();
+// checks the notes are a map of ' ', '.' and '#', then a blank line, then the path
+fn validate(s: &str) -> Result<(), ParseError> {
+    let (map, path) = s.split_once("\n\n").ok_or_else(|| {
+        // point at the path, the first line that can't be part of the map
+        match numbered_lines(s).find(|(_, line)| line.contains(|c| !" .#".contains(c))) {
+            Some((n, line)) => {
+                let first = &line[..line.find(['L', 'R']).unwrap_or(line.len())];
+                ParseError::within(line, first, "a blank line before the path").on_line(n)
+            }
+            None => ParseError::new("", "a blank line and then a path after the map"),
+        }
+    })?;
+    parse_map(map)?;
+    if !map.contains('.') {
+        return Err(ParseError::new("", "a map with at least one open tile"));
+    }
 
-    let column_ranges = (0..cols)
-        .map(|col| {
-            let rows_in_col =
-                tiles
-                    .iter()
-                    .filter_map(|(k, _)| if k.col == col { Some(k.row) } else { None });
-            rows_in_col.clone().min().unwrap()..=rows_in_col.max().unwrap()
-        })
-        .collect();
+    let path_line = map.lines().count() + 2;
+    let path = path.trim();
+    let mut steps = path;
+    while !steps.is_empty() {
+        let len = steps.find(['L', 'R']).unwrap_or(steps.len());
+        let (number, rest) = steps.split_at(len);
+        if number.parse::<usize>().is_err() {
+            let bad = if number.is_empty() {
+                &rest[..1]
+            } else {
+                number
+            };
+            return Err(
+                ParseError::within(path, bad, "a number of tiles to move").on_line(path_line)
+            );
+        }
+        // skip the turn that follows
+        steps = rest.get(1..).unwrap_or_default();
+    }
+    Ok(())
+}
 
-    dbg!(&rows, &cols);
+pub fn parse(s: &str) -> (Map, Vec<String>) {
+    let (map, _) = s
+        .split_once("\n\n")
+        .expect("instructions separated by blank line");
+    let tiles = parse_map(map).expect("the map should be validated");
+    debug!(
+        "map is {} rows by {} columns",
+        tiles.height(),
+        tiles.width()
+    );
 
     let (_, instructions) = s
         .split_once("\n\n")
@@ -184,24 +220,19 @@ fn parse(s: &str) -> (Map, Vec<String>) {
         .map(|x| x.to_string())
         .collect();
 
-    (
-        Map {
-            tiles,
-            row_ranges,
-            column_ranges,
-        },
-        instructions,
-    )
+    (Map { tiles }, instructions)
 }
 
-fn part_1(map: Map, instructions: Vec<String>) -> usize {
+pub fn part_1(map: Map, instructions: Vec<String>) -> usize {
     let mut pc = PC {
         loc: map.get_start(),
         dir: Dir::Right,
         map: &map,
+        trail: Grid::new(map.tiles.width(), map.tiles.height(), None),
     };
+    pc.mark();
 
-    for i in instructions {
+    for (n, i) in instructions.iter().enumerate() {
         if i == "R" {
             pc.turn_right();
         } else if i == "L" {
@@ -209,15 +240,66 @@ fn part_1(map: Map, instructions: Vec<String>) -> usize {
         } else {
             pc.move_ahead(i.parse().unwrap());
         }
+        let title = format!("{} of {} steps along the path", n + 1, instructions.len());
+        render::frame(&title, || draw(&map.tiles, &pc.trail));
+        image::frame("day22-map", |palette| {
+            map_image(&map.tiles, &pc.trail, pc.loc, palette)
+        });
     }
+    render::show("the path round the map", || draw(&map.tiles, &pc.trail));
+    image::save("day22-map", |palette| {
+        map_image(&map.tiles, &pc.trail, pc.loc, palette)
+    });
+
+    debug!("finished at {:?} facing {:?}", pc.loc, pc.dir);
+    1000 * (pc.loc.0 + 1) + 4 * (pc.loc.1 + 1) + pc.dir as usize
+}
 
-    dbg!(1000 * (pc.loc.row + 1) + 4 * (pc.loc.col + 1) + pc.dir as usize)
+// the two parts read the map differently - flat vs. folded into a cube - so keep the notes as
+// text, along with the edge length of the cube's faces (the `face_size` parameter)
+#[derive(Debug)]
+pub struct Notes {
+    pub text: String,
+    pub face_size: usize,
 }
 
-fn main() {
-    let (map, instructions) = parse(include_str!("test.input.txt"));
-    assert_eq!(part_1(map, instructions), 6032);
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 22;
 
-    let (map, instructions) = parse(include_str!("input.txt"));
-    println!("Part 1: {}", part_1(map, instructions));
+    type Input = Notes;
+    type Answer1 = usize;
+    type Answer2 = isize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        validate(s)?;
+        Ok(Notes {
+            text: s.to_string(),
+            face_size: puzzle2::face_size(s),
+        })
+    }
+
+    fn part_1(notes: &Self::Input) -> usize {
+        let (map, instructions) = parse(&notes.text);
+        part_1(map, instructions)
+    }
+
+    fn part_2(notes: &Self::Input) -> isize {
+        let (map, instructions) = puzzle2::parse(&notes.text, notes.face_size);
+        puzzle2::part_2(map, instructions)
+    }
+
+    const PARAMS: &'static [&'static str] = &["face_size"];
+
+    fn with_params(notes: Self::Input, params: &Params) -> Result<Self::Input, ParseError> {
+        let face_size = params.get("face_size", notes.face_size)?;
+        if 6 * face_size * face_size != puzzle2::tile_count(&notes.text) {
+            return Err(ParseError::new(
+                &face_size.to_string(),
+                "a face_size that fits the map onto a cube",
+            ));
+        }
+        Ok(Notes { face_size, ..notes })
+    }
 }
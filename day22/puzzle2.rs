@@ -1,92 +1,98 @@
-use std::collections::HashMap;
-use std::iter::FromIterator;
+use std::collections::VecDeque;
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
-enum Face {
-    One = 1,
-    Two = 2,
-    Three = 3,
-    Four = 4,
-    Five = 5,
-    Six = 6 
+use aoc_common::grid::Pos;
+use aoc_common::{debug, image, render, Grid};
+
+use super::{draw, map_image, parse_map, Tile};
+
+// a direction in 3d, always one of the six unit vectors
+type Axis = [isize; 3];
+
+fn dot(a: Axis, b: Axis) -> isize {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn neg(a: Axis) -> Axis {
+    a.map(|x| -x)
+}
+
+// one square of the net, and where it ended up once the net is folded into a cube
+#[derive(Copy, Clone, Debug)]
+struct Face {
+    // top left corner, in input space
+    origin: (isize, isize),
+    // outward normal
+    normal: Axis,
+    // the face's own right and down directions
+    right: Axis,
+    down: Axis,
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 struct Loc {
-    face: Face,
+    face: usize,
     row: isize,
     col: isize,
 }
 
 impl Loc {
-    fn from_input_space(n: usize, i: usize, j: usize) -> Loc {
-        // match syntax abuse?
-        let face = match i {
-            x if (0..n).contains(&x) => {
-                match j {
-                    y if (n..2*n).contains(&y) => Face::One,
-                    y if (2*n..3*n).contains(&y) => Face::Two,
-                    _ => unreachable!(),
-                }
-            },
-            x if (n..2*n).contains(&x) => Face::Three,
-            x if (2*n..3*n).contains(&x) => {
-                match j {
-                    y if (0..n).contains(&y) => Face::Four,
-                    y if (n..2*n).contains(&y) => Face::Five,
-                    _ => unreachable!(),
-                }
-            },
-            x if (3*n..4*n).contains(&x) => Face::Six,
-            _ => unreachable!(),
-        };
-        Loc { face, row: (i % n) as isize, col: (j % n) as isize }
+    fn to_input_space(self, map: &Map) -> (isize, isize) {
+        let (row, col) = map.faces[self.face].origin;
+        (row + self.row, col + self.col)
     }
 
-    fn to_input_space(&self, n: isize) -> (isize, isize) {
-        match self.face {
-            Face::One => (self.row, n + self.col),
-            Face::Two => (self.row, 2*n + self.col),
-            Face::Three => (n + self.row, n + self.col),
-            Face::Four => (2*n + self.row, self.col),
-            Face::Five => (2*n + self.row, n + self.col),
-            Face::Six => (3*n + self.row, self.col),
-        }
+    // position on the cube, in half tiles from its centre, so every tile lands on an integer
+    fn to_cube_space(self, map: &Map) -> Axis {
+        let n = map.n;
+        let face = map.faces[self.face];
+        std::array::from_fn(|k| {
+            n * face.normal[k]
+                + (2 * self.col + 1 - n) * face.right[k]
+                + (2 * self.row + 1 - n) * face.down[k]
+        })
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum Tile {
-    Open,
-    Solid,
-}
-
 #[derive(Clone, Debug)]
-struct Map {
-    tiles: HashMap<Loc, Tile>,
+pub struct Map {
+    // the flat map, in input space
+    tiles: Grid<Tile>,
+    faces: Vec<Face>,
     n: isize,
 }
 
 impl Map {
     fn get_start(&self) -> Loc {
-        let col = self
-            .tiles
+        let col = (0..self.tiles.width())
+            .find(|&col| self.tiles[(0, col)] == Tile::Open)
+            .unwrap() as isize;
+        let face = self
+            .faces
             .iter()
-            .filter_map(|(k, v)| {
-                if k.face == Face::One && k.row == 0 && *v == Tile::Open {
-                    Some(k.col)
-                } else {
-                    None
-                }
-            })
-            .min()
+            .position(|f| f.origin == (0, col - col % self.n))
             .unwrap();
-        Loc { face: Face::One, row: 0, col }
+        Loc {
+            face,
+            row: 0,
+            col: col % self.n,
+        }
+    }
+
+    fn tile(&self, loc: Loc) -> Tile {
+        let (row, col) = loc.to_input_space(self);
+        self.tiles[(row as usize, col as usize)]
+    }
+
+    fn face_with_normal(&self, normal: Axis) -> usize {
+        self.faces
+            .iter()
+            .position(|f| f.normal == normal)
+            .expect("the net should fold into a cube")
     }
 }
 
 // direction is *within that face*
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 enum Dir {
     Right = 0,
     Up = 3,
@@ -94,21 +100,63 @@ enum Dir {
     Down = 1,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl Dir {
+    fn on_face(self, face: &Face) -> Axis {
+        match self {
+            Dir::Right => face.right,
+            Dir::Left => neg(face.right),
+            Dir::Down => face.down,
+            Dir::Up => neg(face.down),
+        }
+    }
+
+    // as seen on the flat map, where every face keeps the same way up
+    fn arrow(self) -> char {
+        match self {
+            Dir::Right => '>',
+            Dir::Up => '^',
+            Dir::Left => '<',
+            Dir::Down => 'v',
+        }
+    }
+
+    fn from_face(face: &Face, axis: Axis) -> Dir {
+        [Dir::Right, Dir::Left, Dir::Down, Dir::Up]
+            .into_iter()
+            .find(|d| d.on_face(face) == axis)
+            .expect("axis should lie in the face")
+    }
+}
+
+#[derive(Clone, Debug)]
 struct PC<'a> {
     loc: Loc,
     dir: Dir,
     map: &'a Map,
+    // in input space
+    trail: Grid<Option<char>>,
 }
 
 impl<'a> PC<'a> {
+    // where it is on the flat map
+    fn pos(&self) -> Pos {
+        let (row, col) = self.loc.to_input_space(self.map);
+        (row as usize, col as usize)
+    }
+
+    fn mark(&mut self) {
+        let pos = self.pos();
+        self.trail[pos] = Some(self.dir.arrow());
+    }
+
     fn turn_left(&mut self) {
         self.dir = match self.dir {
             Dir::Right => Dir::Up,
             Dir::Up => Dir::Left,
             Dir::Left => Dir::Down,
             Dir::Down => Dir::Right,
-        }
+        };
+        self.mark();
     }
 
     fn turn_right(&mut self) {
@@ -117,28 +165,43 @@ impl<'a> PC<'a> {
             Dir::Down => Dir::Left,
             Dir::Left => Dir::Up,
             Dir::Up => Dir::Right,
-        }
+        };
+        self.mark();
     }
 
     fn move_ahead(&mut self, n: isize) {
         (0..n).for_each(|_| {
             let (nxt_loc, nxt_dir) = self.next_loc();
-            match self.map.tiles.get(&nxt_loc).unwrap() {
+            match self.map.tile(nxt_loc) {
                 Tile::Open => {
                     self.loc = nxt_loc;
                     self.dir = nxt_dir;
-                },
+                    self.mark();
+                }
                 Tile::Solid => (),
+                Tile::Void => unreachable!("every face is on the map"),
             };
         })
     }
 
     fn next_loc(&mut self) -> (Loc, Dir) {
         let next = match self.dir {
-            Dir::Right => Loc { face: self.loc.face, row: self.loc.row, col: self.loc.col + 1 },
-            Dir::Left => Loc { face: self.loc.face, row: self.loc.row, col: self.loc.col - 1 },
-            Dir::Up => Loc { face: self.loc.face, row: self.loc.row - 1, col: self.loc.col },
-            Dir::Down => Loc { face: self.loc.face, row: self.loc.row + 1, col: self.loc.col },
+            Dir::Right => Loc {
+                col: self.loc.col + 1,
+                ..self.loc
+            },
+            Dir::Left => Loc {
+                col: self.loc.col - 1,
+                ..self.loc
+            },
+            Dir::Up => Loc {
+                row: self.loc.row - 1,
+                ..self.loc
+            },
+            Dir::Down => Loc {
+                row: self.loc.row + 1,
+                ..self.loc
+            },
         };
 
         self.wrap_loc(next)
@@ -146,190 +209,127 @@ impl<'a> PC<'a> {
 
     fn wrap_loc(&self, loc: Loc) -> (Loc, Dir) {
         let n = self.map.n;
-        if (0..n).contains(&loc.row) && (0..n).contains(&loc.col) { return (loc, self.dir) }
-
-        let (loc, dir) = if loc.col >= n {
-            // moving off the face to the RIGHT
-            match loc.face {
-                Face::One => {
-                    (
-                        Loc { face: Face::Two, row: loc.row, col: 0},
-                        Dir::Right,
-                    )
-                },
-                Face::Two => {
-                    (
-                        Loc { face: Face::Five, row: n - 1 - loc.row, col: n - 1 },
-                        Dir::Left,
-                    )
-                },
-                Face::Three => {
-                    (
-                        Loc { face: Face::Two, row: n - 1, col: loc.row },
-                        Dir::Up,
-                    )
-                },
-                Face::Four => {
-                    (
-                        Loc { face: Face::Five, row: loc.row, col: 0 },
-                        Dir::Right,
-                    )
-                },
-                Face::Five => {
-                    (
-                        Loc { face: Face::Two, row: n - 1 - loc.row, col: n - 1 },
-                        Dir::Left,
-                    )
-                },
-                Face::Six => {
-                    (
-                        Loc { face: Face::Five, row: n - 1, col: loc.row },
-                        Dir::Up,
-                    )
-                },
-            }
-        } else if loc.col < 0 {
-            // moving off the face to the LEFT
-            match loc.face {
-                Face::One => {
-                    (
-                        Loc { face: Face::Four, row: n - 1 - loc.row, col: 0},
-                        Dir::Right,
-                    )
-                },
-                Face::Two => {
-                    (
-                        Loc { face: Face::One, row: loc.row, col: n - 1 },
-                        Dir::Left,
-                    )
-                },
-                Face::Three => {
-                    (
-                        Loc { face: Face::Four, row: 0, col: loc.row },
-                        Dir::Down,
-                    )
-                },
-                Face::Four => {
-                    (
-                        Loc { face: Face::One, row: n - 1 - loc.row, col: 0 },
-                        Dir::Right,
-                    )
-                },
-                Face::Five => {
-                    (
-                        Loc { face: Face::Four, row: loc.row, col: n - 1 },
-                        Dir::Left,
-                    )
-                },
-                Face::Six => {
-                    (
-                        Loc { face: Face::One, row: 0, col: loc.row },
-                        Dir::Down,
-                    )
-                },
-            }
-        } else if loc.row < 0 {
-            // moving off the face to the TOP
-            match loc.face {
-                Face::One => {
-                    (
-                        Loc { face: Face::Six, row: loc.col, col: 0},
-                        Dir::Right,
-                    )
-                },
-                Face::Two => {
-                    (
-                        Loc { face: Face::Six, row: n - 1, col: loc.col },
-                        Dir::Up,
-                    )
-                },
-                Face::Three => {
-                    (
-                        Loc { face: Face::One, row: n - 1, col: loc.col },
-                        Dir::Up,
-                    )
-                },
-                Face::Four => {
-                    (
-                        Loc { face: Face::Three, row: loc.col, col: 0 },
-                        Dir::Right,
-                    )
-                },
-                Face::Five => {
-                    (
-                        Loc { face: Face::Three, row: n - 1, col: loc.col },
-                        Dir::Up,
-                    )
-                },
-                Face::Six => {
-                    (
-                        Loc { face: Face::Four, row: n - 1, col: loc.col },
-                        Dir::Up,
-                    )
-                },
-            }
-        } else if loc.row >= n {
-            // moving off the face to the BOTTOM
-            match loc.face {
-                Face::One => {
-                    (
-                        Loc { face: Face::Three, row: 0, col: loc.col},
-                        Dir::Down,
-                    )
-                },
-                Face::Two => {
-                    (
-                        Loc { face: Face::Three, row: loc.col, col: n - 1 },
-                        Dir::Left,
-                    )
-                },
-                Face::Three => {
-                    (
-                        Loc { face: Face::Five, row: 0, col: loc.col },
-                        Dir::Down,
-                    )
+        if (0..n).contains(&loc.row) && (0..n).contains(&loc.col) {
+            return (loc, self.dir);
+        }
+
+        // going over the edge, we land on the face that points the way we were heading, and
+        // carry on heading away from the face we left
+        let from = self.map.faces[self.loc.face];
+        let heading = self.dir.on_face(&from);
+        let face = self.map.face_with_normal(heading);
+        let to = self.map.faces[face];
+
+        // only the position along the edge carries over: the new tile sits on the new face,
+        // one step in from the edge
+        let p = self.loc.to_cube_space(self.map);
+        let (a, b) = (dot(p, from.normal), dot(p, heading));
+        let q: Axis =
+            std::array::from_fn(|k| p[k] + (n - 1 - a) * from.normal[k] + (n - b) * heading[k]);
+        let loc = Loc {
+            face,
+            row: (dot(q, to.down) + n - 1) / 2,
+            col: (dot(q, to.right) + n - 1) / 2,
+        };
+
+        (loc, Dir::from_face(&to, neg(from.normal)))
+    }
+}
+
+// open tiles and walls on the map, leaving out the void around the net
+pub fn tile_count(s: &str) -> usize {
+    s.lines()
+        .take_while(|line| !line.is_empty())
+        .flat_map(|line| line.chars())
+        .filter(|c| *c == '.' || *c == '#')
+        .count()
+}
+
+// the cube has six square faces, so the edge length falls out of the number of tiles
+pub fn face_size(s: &str) -> usize {
+    ((tile_count(s) / 6) as f64).sqrt() as usize
+}
+
+// walks the net from the first face, rolling the cube over each shared edge
+fn fold(origins: Vec<(isize, isize)>, n: isize) -> Vec<Face> {
+    let mut faces: Vec<Option<Face>> = vec![None; origins.len()];
+    faces[0] = Some(Face {
+        origin: origins[0],
+        normal: [0, 0, -1],
+        right: [1, 0, 0],
+        down: [0, 1, 0],
+    });
+
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        let face = faces[i].unwrap();
+        let (row, col) = face.origin;
+        let neighbours = [
+            (
+                (row, col + n),
+                Face {
+                    normal: face.right,
+                    right: neg(face.normal),
+                    ..face
                 },
-                Face::Four => {
-                    (
-                        Loc { face: Face::Six, row: 0, col: loc.col },
-                        Dir::Down,
-                    )
+            ),
+            (
+                (row, col - n),
+                Face {
+                    normal: neg(face.right),
+                    right: face.normal,
+                    ..face
                 },
-                Face::Five => {
-                    (
-                        Loc { face: Face::Six, row: loc.col, col: n - 1 },
-                        Dir::Left,
-                    )
+            ),
+            (
+                (row + n, col),
+                Face {
+                    normal: face.down,
+                    down: neg(face.normal),
+                    ..face
                 },
-                Face::Six => {
-                    (
-                        Loc { face: Face::Two, row: 0, col: loc.col },
-                        Dir::Down,
-                    )
+            ),
+            (
+                (row - n, col),
+                Face {
+                    normal: neg(face.down),
+                    down: face.normal,
+                    ..face
                 },
+            ),
+        ];
+        for (origin, next) in neighbours {
+            if let Some(j) = origins.iter().position(|o| *o == origin) {
+                if faces[j].is_none() {
+                    faces[j] = Some(Face { origin, ..next });
+                    queue.push_back(j);
+                }
             }
-        } else {
-            unreachable!();
-        };
-
-        (loc, dir)
+        }
     }
+
+    faces
+        .into_iter()
+        .map(|f| f.expect("the net should be connected"))
+        .collect()
 }
 
-fn parse(s: &str, n: usize) -> (Map, Vec<String>) {
-    let tiles = HashMap::from_iter(s.lines().take_while(|x| *x != "\n").enumerate().flat_map(
-        |(i, line)| {
-            line.chars().enumerate().filter_map(move |(j, c)| match c {
-                '.' => Some((Loc::from_input_space(n, i, j), Tile::Open)),
-                '#' => Some((Loc::from_input_space(n, i, j), Tile::Solid)),
-                _ => None,
-            })
-        },
-    ));
-
-    let (_, instructions) = s
+pub fn parse(s: &str, n: usize) -> (Map, Vec<String>) {
+    let (net, instructions) = s
         .split_once("\n\n")
         .expect("instructions separated by blank line");
+    let tiles = parse_map(net).expect("the map should be validated");
+
+    // faces in reading order, so the first one holds the start
+    let origins: Vec<(isize, isize)> = (0..tiles.height())
+        .step_by(n)
+        .flat_map(|i| (0..tiles.width()).step_by(n).map(move |j| (i, j)))
+        .filter(|pos| tiles[*pos] != Tile::Void)
+        .map(|(i, j)| (i as isize, j as isize))
+        .collect();
+    let n = n as isize;
+    let faces = fold(origins, n);
+
     let instructions = instructions.replace('R', " R ");
     let instructions = instructions.replace('L', " L ");
     let instructions = instructions
@@ -338,23 +338,19 @@ fn parse(s: &str, n: usize) -> (Map, Vec<String>) {
         .map(|x| x.to_string())
         .collect();
 
-    (
-        Map {
-            tiles,
-            n: n as isize,
-        },
-        instructions,
-    )
+    (Map { tiles, faces, n }, instructions)
 }
 
-fn part_2(map: Map, instructions: Vec<String>) -> isize {
+pub fn part_2(map: Map, instructions: Vec<String>) -> isize {
     let mut pc = PC {
         loc: map.get_start(),
         dir: Dir::Right,
         map: &map,
+        trail: Grid::new(map.tiles.width(), map.tiles.height(), None),
     };
+    pc.mark();
 
-    for i in instructions.iter() {
+    for (n, i) in instructions.iter().enumerate() {
         if i == "R" {
             pc.turn_right();
         } else if i == "L" {
@@ -362,16 +358,18 @@ fn part_2(map: Map, instructions: Vec<String>) -> isize {
         } else {
             pc.move_ahead(i.parse().unwrap());
         }
+        let title = format!("{} of {} steps along the path", n + 1, instructions.len());
+        render::frame(&title, || draw(&map.tiles, &pc.trail));
+        image::frame("day22-cube", |palette| {
+            map_image(&map.tiles, &pc.trail, pc.pos(), palette)
+        });
     }
+    render::show("the path round the cube", || draw(&map.tiles, &pc.trail));
+    image::save("day22-cube", |palette| {
+        map_image(&map.tiles, &pc.trail, pc.pos(), palette)
+    });
 
-    let (row, col) = pc.loc.to_input_space(map.n);
-    dbg!(1000 * (row + 1) + 4 * (col + 1) + pc.dir as isize)
-}
-
-fn main() {
-    // let (map, instructions) = parse(include_str!("test.input.txt"), 4);
-    // assert_eq!(part_2(map, instructions), 5031);
-
-    let (map, instructions) = parse(include_str!("input.txt"), 50);
-    println!("Part 2: {}", part_2(map, instructions));
+    let (row, col) = pc.loc.to_input_space(&map);
+    debug!("finished at {:?} facing {:?}", pc.loc, pc.dir);
+    1000 * (row + 1) + 4 * (col + 1) + pc.dir as isize
 }
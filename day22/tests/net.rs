@@ -0,0 +1,18 @@
+use aoc_common::Solution;
+use day22::Puzzle;
+
+// a full-size map on the net the old solver's wrap table was written for, with the answers
+// that solver gave, to check the folding solver against it
+const NET: &str = include_str!("../net.txt");
+
+#[test]
+fn part_1() {
+    let input = Puzzle::parse(NET).unwrap();
+    assert_eq!(Puzzle::part_1(&input), 40302);
+}
+
+#[test]
+fn part_2() {
+    let input = Puzzle::parse(NET).unwrap();
+    assert_eq!(Puzzle::part_2(&input), 193102);
+}
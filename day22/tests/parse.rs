@@ -0,0 +1,13 @@
+use aoc_common::Solution;
+use day22::Puzzle;
+
+const NET: &str = include_str!("../net.txt");
+
+#[test]
+fn errors_point_at_a_line_instead_of_quoting_the_input() {
+    let error = Puzzle::parse(&NET.replace("\n\n", "\n")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 201, column 1: expected a blank line before the path, found \"1\""
+    );
+}
@@ -0,0 +1,17 @@
+//! Random section assignments.
+
+use aoc_common::rng::Rng;
+
+/// About as many pairs as a real input has.
+pub const SIZE: usize = 1000;
+
+/// `size` pairs of elves' assignments, each a range of sections 1 to 99.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut assignment = || {
+        let start = rng.range(1..=99u32);
+        format!("{start}-{}", rng.range(start..=99))
+    };
+    (0..size.max(1))
+        .map(|_| format!("{},{}\n", assignment(), assignment()))
+        .collect()
+}
@@ -1,58 +1,75 @@
-use std::num::ParseIntError;
-use std::ops::RangeInclusive;
+pub mod generate;
+
 use std::str::FromStr;
 
+use aoc_common::{numbered_lines, IntervalSet, ParseError, Solution};
+
 #[derive(Debug)]
-struct Assignment(RangeInclusive<usize>);
+pub struct Assignment(IntervalSet);
 
 impl FromStr for Assignment {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once('-').unwrap();
-        Ok(Assignment(start.parse()?..=end.parse()?))
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| ParseError::new(s, "<start>-<end>"))?;
+        let section = |id: &str| {
+            id.parse::<isize>()
+                .ok()
+                .filter(|&id| id >= 0)
+                .ok_or_else(|| ParseError::within(s, id, "a section id"))
+        };
+        let (start, end) = (section(start)?, section(end)?);
+        // a reversed range would be an empty set, which every other assignment contains
+        if start > end {
+            return Err(ParseError::new(s, "<start>-<end> with start <= end"));
+        }
+        Ok(Assignment(IntervalSet::from(start..=end)))
     }
 }
 
 impl Assignment {
     fn overlaps(&self, other: &Assignment) -> bool {
-        other.0.contains(&self.0.start())
-            || other.0.contains(&self.0.end())
-            || self.0.contains(&other.0.start())
-            || self.0.contains(&other.0.end())
+        !self.0.intersection(&other.0).is_empty()
     }
 
     fn contains_entirely(&self, other: &Assignment) -> bool {
-        self.0.start() <= other.0.start() && self.0.end() >= other.0.end()
+        other.0.difference(&self.0).is_empty()
     }
 }
 
-fn main() {
-    // part 1
-    let n = include_str!("input.txt")
-        .lines()
-        .map(|line| line.split_once(',').unwrap())
-        .map(|(a, b)| {
-            (
-                a.parse::<Assignment>().unwrap(),
-                b.parse::<Assignment>().unwrap(),
-            )
-        })
-        .filter(|(a, b)| a.contains_entirely(&b) || b.contains_entirely(&a))
-        .count();
-    println!("Part 1: {n}");
-
-    // part 2
-    let n = include_str!("input.txt")
-        .lines()
-        .map(|line| line.split_once(',').unwrap())
-        .map(|(a, b)| {
-            (
-                a.parse::<Assignment>().unwrap(),
-                b.parse::<Assignment>().unwrap(),
-            )
-        })
-        .filter(|(a, b)| a.overlaps(&b))
-        .count();
-    println!("Part 2: {n}");
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 4;
+
+    type Input = Vec<(Assignment, Assignment)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        numbered_lines(s)
+            .map(|(n, line)| {
+                let (a, b) = line.split_once(',').ok_or_else(|| {
+                    ParseError::new(line, "<start>-<end>,<start>-<end>").on_line(n)
+                })?;
+                Ok((
+                    a.parse().map_err(|e: ParseError| e.at(n, line, a))?,
+                    b.parse().map_err(|e: ParseError| e.at(n, line, b))?,
+                ))
+            })
+            .collect()
+    }
+
+    fn part_1(pairs: &Self::Input) -> usize {
+        pairs
+            .iter()
+            .filter(|(a, b)| a.contains_entirely(b) || b.contains_entirely(a))
+            .count()
+    }
+
+    fn part_2(pairs: &Self::Input) -> usize {
+        pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
+    }
 }
@@ -0,0 +1,13 @@
+use aoc_common::Solution;
+use day4::Puzzle;
+
+#[test]
+fn ranges_run_forwards() {
+    let error = Puzzle::parse("2-4,6-8\n1-2,5-3").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 5: expected <start>-<end> with start <= end, found \"5-3\""
+    );
+    let input = Puzzle::parse("1-2,3-3").unwrap();
+    assert_eq!(Puzzle::part_1(&input), 0);
+}
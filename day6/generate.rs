@@ -0,0 +1,31 @@
+//! Random datastreams.
+
+use aoc_common::rng::Rng;
+
+/// About as long as a real datastream.
+pub const SIZE: usize = 4096;
+
+// the length of a start-of-message marker
+const MARKER: usize = 14;
+
+/// A datastream of `size` letters. They're picked from a few at a time, so markers don't turn
+/// up by chance, and then a start-of-message marker goes somewhere past the first quarter.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut letters: Vec<u8> = (b'a'..=b'z').collect();
+    let mut stream = Vec::new();
+    while stream.len() < size {
+        rng.shuffle(&mut letters);
+        let few = rng.range(2..=8usize);
+        for _ in 0..rng.range(1..=40usize) {
+            stream.push(*rng.pick(&letters[..few]));
+        }
+    }
+    stream.truncate(size);
+    if size >= MARKER {
+        rng.shuffle(&mut letters);
+        let at = rng.range(size / 4..=size - MARKER);
+        stream[at..at + MARKER].copy_from_slice(&letters[..MARKER]);
+    }
+    String::from_utf8(stream).expect("the stream is all letters") + "\n"
+}
@@ -1,9 +1,14 @@
+pub mod generate;
+
 use std::collections::HashSet;
 
-fn find_marker(input: &str, marker_len: usize) -> usize {
+use aoc_common::{ParseError, Solution};
+
+pub fn find_marker(input: &str, marker_len: usize) -> usize {
     let mut unique = HashSet::new();
     marker_len
-        + input.as_bytes()
+        + input
+            .as_bytes()
             .windows(marker_len)
             .take_while(|x| {
                 unique.clear();
@@ -12,22 +17,62 @@ fn find_marker(input: &str, marker_len: usize) -> usize {
             .count()
 }
 
-fn main() {
-    // part 1
-    assert!(find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4) == 7);
-    assert!(find_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4) == 5);
-    assert!(find_marker("nppdvjthqldpwncqszvftbrmjlhg", 4) == 6);
-    assert!(find_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4) == 10);
-    assert!(find_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4) == 11);
-    let n = find_marker(include_str!("input.txt"), 4);
-    println!("Part 1: {n}");
-
-    // part 2
-    assert!(find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14) == 19);
-    assert!(find_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14) == 23);
-    assert!(find_marker("nppdvjthqldpwncqszvftbrmjlhg", 14) == 23);
-    assert!(find_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14) == 29);
-    assert!(find_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14) == 26);
-    let n = find_marker(include_str!("input.txt"), 14);
-    println!("Part 2: {n}");
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let datastream = s.trim();
+        if datastream.is_empty() {
+            return Err(ParseError::new("", "a datastream of letters"));
+        }
+        match datastream
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_lowercase())
+        {
+            Some((i, c)) => {
+                let bad = &datastream[i..i + c.len_utf8()];
+                Err(ParseError::within(datastream, bad, "a lowercase letter").on_line(1))
+            }
+            None => Ok(datastream.to_string()),
+        }
+    }
+
+    fn part_1(datastream: &Self::Input) -> usize {
+        find_marker(datastream, 4)
+    }
+
+    fn part_2(datastream: &Self::Input) -> usize {
+        find_marker(datastream, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_marker;
+
+    const STREAMS: [&str; 5] = [
+        "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+        "bvwbjplbgvbhsrlpgdmjqwftvncz",
+        "nppdvjthqldpwncqszvftbrmjlhg",
+        "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+        "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+    ];
+
+    #[test]
+    fn start_of_packet() {
+        let found: Vec<usize> = STREAMS.iter().map(|s| find_marker(s, 4)).collect();
+        assert_eq!(found, [7, 5, 6, 10, 11]);
+    }
+
+    #[test]
+    fn start_of_message() {
+        let found: Vec<usize> = STREAMS.iter().map(|s| find_marker(s, 14)).collect();
+        assert_eq!(found, [19, 23, 23, 29, 26]);
+    }
 }
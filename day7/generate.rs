@@ -0,0 +1,98 @@
+//! Random terminal sessions exploring a filesystem.
+
+use aoc_common::rng::Rng;
+
+/// About as many files as a real input has.
+pub const SIZE: usize = 300;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(usize, String)>,
+}
+
+// a name none of `taken` has, with an extension now and then
+fn name(rng: &mut Rng, taken: &[&str], extension: bool) -> String {
+    loop {
+        let len = rng.range(1..=8usize);
+        let mut name: String = (0..len)
+            .map(|_| char::from(rng.range(b'a'..=b'z')))
+            .collect();
+        if extension && rng.chance(0.6) {
+            name += *rng.pick(&[".txt", ".dat", ".log", ".lst"]);
+        }
+        if !taken.contains(&name.as_str()) {
+            return name;
+        }
+    }
+}
+
+fn names(dirs: &[Dir], dir: &Dir) -> Vec<String> {
+    let files = dir.files.iter().map(|(_, name)| name.clone());
+    dir.dirs
+        .iter()
+        .map(|&d| dirs[d].name.clone())
+        .chain(files)
+        .collect()
+}
+
+/// A session that `cd`s into and lists every directory of a filesystem holding `size` files,
+/// taking up 42 to 58 million of a 70,000,000 disk like a real input, so there's something to
+/// delete to make room for the update.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    // the files share out the total in random proportions
+    let total: usize = rng.range(42_000_000..=58_000_000);
+    let weights: Vec<usize> = (0..size).map(|_| rng.range(1..=1000)).collect();
+    let weight: usize = weights.iter().sum();
+    let mut dirs = vec![Dir {
+        name: "/".to_string(),
+        ..Dir::default()
+    }];
+    for _ in 0..size / 3 {
+        let parent = rng.range(0..dirs.len());
+        let taken = names(&dirs, &dirs[parent]);
+        let taken: Vec<&str> = taken.iter().map(String::as_str).collect();
+        let name = name(rng, &taken, false);
+        dirs.push(Dir {
+            name,
+            ..Dir::default()
+        });
+        let child = dirs.len() - 1;
+        dirs[parent].dirs.push(child);
+    }
+    for w in weights {
+        let dir = rng.range(0..dirs.len());
+        let taken = names(&dirs, &dirs[dir]);
+        let taken: Vec<&str> = taken.iter().map(String::as_str).collect();
+        let name = name(rng, &taken, true);
+        let file_size = (total * w / weight).max(1);
+        dirs[dir].files.push((file_size, name));
+    }
+
+    let mut session = String::new();
+    explore(rng, &dirs, 0, &mut session);
+    session
+}
+
+fn explore(rng: &mut Rng, dirs: &[Dir], d: usize, session: &mut String) {
+    let dir = &dirs[d];
+    session.push_str(&format!("$ cd {}\n$ ls\n", dir.name));
+    let mut listing: Vec<String> = dir
+        .dirs
+        .iter()
+        .map(|&child| format!("dir {}\n", dirs[child].name))
+        .chain(
+            dir.files
+                .iter()
+                .map(|(size, name)| format!("{size} {name}\n")),
+        )
+        .collect();
+    rng.shuffle(&mut listing);
+    session.extend(listing);
+    for &child in &dir.dirs {
+        explore(rng, dirs, child, session);
+        session.push_str("$ cd ..\n");
+    }
+}
@@ -1,118 +1,223 @@
+pub mod generate;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::str::FromStr;
 
+use aoc_common::{numbered_lines, Params, ParseError, Solution};
+
 #[derive(Debug)]
-enum Command {
+pub enum Command {
     ListDir,
     ChangeDir(Option<String>),
 }
 
 impl FromStr for Command {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cmd = s.strip_prefix("$ ").ok_or("not a command")?;
+        let cmd = s
+            .strip_prefix("$ ")
+            .ok_or_else(|| ParseError::new(s, "a command starting with \"$ \""))?;
         match cmd.split_once(' ') {
-            Some((_, "..")) => Ok(Command::ChangeDir(None)),
-            Some((_, dir)) => Ok(Command::ChangeDir(Some(dir.to_string()))),
-            None => Ok(Command::ListDir),
+            Some(("cd", "..")) => Ok(Command::ChangeDir(None)),
+            Some(("cd", dir)) => Ok(Command::ChangeDir(Some(dir.to_string()))),
+            None if cmd == "ls" => Ok(Command::ListDir),
+            _ => Err(ParseError::within(s, cmd, "ls or cd <dir>")),
         }
     }
 }
 
 #[derive(Debug)]
-struct File {
+pub struct File {
     size: usize,
     name: String,
 }
 
 impl FromStr for File {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.split_once(' ') {
             Some((size, name)) => Ok(File {
-                size: size.parse().map_err(|_| "bad file size")?,
+                size: size
+                    .parse()
+                    .map_err(|_| ParseError::within(s, size, "a file size"))?,
                 name: name.to_string(),
             }),
-            None => Err("not a file".to_string()),
+            None => Err(ParseError::new(s, "<size> <name> or dir <name>")),
         }
     }
 }
 
-fn main() {
-    assert!(part_1(include_str!("test.input.txt")) == 95437);
-    println!("Part 1: {}", part_1(include_str!("input.txt")));
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::ListDir => write!(f, "$ ls"),
+            Command::ChangeDir(None) => write!(f, "$ cd .."),
+            Command::ChangeDir(Some(dir)) => write!(f, "$ cd {dir}"),
+        }
+    }
+}
 
-    assert!(part_2(include_str!("test.input.txt")) == 24933642);
-    println!("Part 2: {}", part_2(include_str!("input.txt")));
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.size, self.name)
+    }
 }
 
-fn part_1(input: &str) -> usize {
-    let mut dir_stack = Vec::<String>::new();
-    let mut dir_sizes = std::collections::HashMap::<String, usize>::new();
-    let mut seen = std::collections::HashSet::<String>::new();
-    input.lines().for_each(|line| {
-        match line.parse::<Command>() {
-            Ok(Command::ListDir) => (),
-            Ok(Command::ChangeDir(None)) => {
-                dir_stack.pop();
-            }
-            Ok(Command::ChangeDir(Some(dir))) => dir_stack.push(dir),
-            _ => {
-                // not a command, parse the listing as a (maybe) file
-                if let Ok(file) = line.parse::<File>() {
-                    if seen.insert(format!("{}/{}", dir_stack.join("/"), file.name)) {
-                        let mut full_path = Vec::<String>::new();
-                        for d in &dir_stack {
-                            full_path.push(d.to_string());
-                            dir_sizes
-                                .entry(full_path.join("/").clone())
-                                .and_modify(|e| *e += file.size)
-                                .or_insert(file.size);
-                        }
-                    }
-                }
-            }
+/// A line of the terminal output: a command, or a directory or file it listed.
+#[derive(Debug)]
+pub enum Line {
+    Command(Command),
+    Dir(String),
+    File(File),
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with('$') {
+            s.parse().map(Line::Command)
+        } else if let Some(dir) = s.strip_prefix("dir ") {
+            Ok(Line::Dir(dir.to_string()))
+        } else {
+            // not a command or a directory, parse the listing as a file
+            s.parse().map(Line::File)
         }
-    });
-    dir_sizes
-        .into_values()
-        .fold(0, |acc, x| if x <= 100_000 { acc + x } else { acc })
+    }
 }
 
-fn part_2(input: &str) -> usize {
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Command(command) => write!(f, "{command}"),
+            Line::Dir(dir) => write!(f, "dir {dir}"),
+            Line::File(file) => write!(f, "{file}"),
+        }
+    }
+}
+
+// total size of every directory, keyed by its full path
+pub fn parse(s: &str) -> Result<HashMap<String, usize>, ParseError> {
     let mut dir_stack = Vec::<String>::new();
-    let mut dir_sizes = std::collections::HashMap::<String, usize>::new();
-    let mut seen = std::collections::HashSet::<String>::new();
-    input.lines().for_each(|line| {
-        match line.parse::<Command>() {
-            Ok(Command::ListDir) => (),
-            Ok(Command::ChangeDir(None)) => {
+    let mut dir_sizes = HashMap::<String, usize>::new();
+    let mut seen = HashSet::<String>::new();
+    for (n, line) in numbered_lines(s) {
+        match line.parse::<Line>().map_err(|e| e.on_line(n))? {
+            Line::Command(Command::ListDir) => (),
+            Line::Command(Command::ChangeDir(None)) => {
                 dir_stack.pop();
             }
-            Ok(Command::ChangeDir(Some(dir))) => dir_stack.push(dir),
-            _ => {
-                // not a command, parse the listing as a (maybe) file
-                if let Ok(file) = line.parse::<File>() {
-                    if seen.insert(format!("{}/{}", dir_stack.join("/"), file.name)) {
-                        let mut full_path = Vec::<String>::new();
-                        for d in &dir_stack {
-                            full_path.push(d.to_string());
-                            dir_sizes
-                                .entry(full_path.join("/").clone())
-                                .and_modify(|e| *e += file.size)
-                                .or_insert(file.size);
-                        }
+            Line::Command(Command::ChangeDir(Some(dir))) => dir_stack.push(dir),
+            // directories only count once we cd into them
+            Line::Dir(_) => (),
+            Line::File(file) => {
+                if seen.insert(format!("{}/{}", dir_stack.join("/"), file.name)) {
+                    let mut full_path = Vec::<String>::new();
+                    for d in &dir_stack {
+                        full_path.push(d.to_string());
+                        let total = dir_sizes.entry(full_path.join("/")).or_insert(0);
+                        *total = total.checked_add(file.size).ok_or_else(|| {
+                            ParseError::new(line, "a file size that fits in its directory's total")
+                                .on_line(n)
+                        })?;
                     }
                 }
             }
         }
-    });
-    // total FS size 70_000_000
-    // min unused space 30_000_000
-    let total_used = *dir_sizes.get(&"/".to_string()).unwrap();
-    let to_delete = 30_000_000 - (70_000_000 - total_used);
+    }
+    if !dir_sizes.contains_key("/") {
+        return Err(ParseError::new("", "a listing with files under /"));
+    }
+    Ok(dir_sizes)
+}
+
+pub fn part_1(dir_sizes: &HashMap<String, usize>) -> usize {
     dir_sizes
-        .into_values()
-        .fold(usize::MAX, |acc, x| if x >= to_delete { acc.min(x) } else { acc })
+        .values()
+        .fold(0, |acc, &x| if x <= 100_000 { acc + x } else { acc })
+}
+
+// the files have to fit on the disk and the update can't need more than all of it, which
+// `Filesystem::fits` checks, so deleting / always frees up enough
+pub fn part_2(dir_sizes: &HashMap<String, usize>, disk_size: usize, space_needed: usize) -> usize {
+    let to_delete = space_needed.saturating_sub(disk_size - dir_sizes["/"]);
+    dir_sizes
+        .values()
+        .copied()
+        .filter(|&x| x >= to_delete)
+        .min()
+        .expect("/ is big enough to delete")
+}
+
+// the disk's size and the free space the update needs aren't in the terminal output; they're the
+// `disk_size` and `space_needed` parameters
+#[derive(Debug)]
+pub struct Filesystem {
+    pub dir_sizes: HashMap<String, usize>,
+    pub disk_size: usize,
+    pub space_needed: usize,
+}
+
+impl Filesystem {
+    // whether the files fit on the disk with room to make for the update
+    fn fits(self) -> Result<Self, ParseError> {
+        let used = self.dir_sizes["/"];
+        if self.disk_size < used {
+            return Err(ParseError::new(
+                &self.disk_size.to_string(),
+                &format!("a disk_size of at least {used}, big enough for every file"),
+            ));
+        }
+        if self.space_needed > self.disk_size {
+            return Err(ParseError::new(
+                &self.space_needed.to_string(),
+                &format!(
+                    "a space_needed of at most the disk_size, {}",
+                    self.disk_size
+                ),
+            ));
+        }
+        Ok(self)
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 7;
+
+    type Input = Filesystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Filesystem {
+            dir_sizes: parse(s)?,
+            disk_size: 70_000_000,
+            space_needed: 30_000_000,
+        }
+        .fits()
+    }
+
+    fn part_1(fs: &Self::Input) -> usize {
+        part_1(&fs.dir_sizes)
+    }
+
+    fn part_2(fs: &Self::Input) -> usize {
+        part_2(&fs.dir_sizes, fs.disk_size, fs.space_needed)
+    }
+
+    const PARAMS: &'static [&'static str] = &["disk_size", "space_needed"];
+
+    fn with_params(fs: Self::Input, params: &Params) -> Result<Self::Input, ParseError> {
+        Filesystem {
+            disk_size: params.get("disk_size", fs.disk_size)?,
+            space_needed: params.get("space_needed", fs.space_needed)?,
+            ..fs
+        }
+        .fits()
+    }
 }
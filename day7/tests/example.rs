@@ -0,0 +1,33 @@
+use aoc_common::{Params, Solution};
+use day7::Puzzle;
+
+const EXAMPLE: &str = include_str!("../test.input.txt");
+
+#[test]
+fn part_1() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_1(&input), 95437);
+}
+
+#[test]
+fn part_2() {
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    assert_eq!(Puzzle::part_2(&input), 24933642);
+}
+
+#[test]
+fn the_files_and_the_update_fit_on_the_disk() {
+    let error = Puzzle::parse("$ cd /\n$ ls\n80000000 a").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a disk_size of at least 80000000, big enough for every file, found \"70000000\""
+    );
+
+    let input = Puzzle::parse(EXAMPLE).unwrap();
+    let params: Params = [("space_needed", "70000001")].into_iter().collect();
+    let error = Puzzle::with_params(input, &params).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a space_needed of at most the disk_size, 70000000, found \"70000001\""
+    );
+}
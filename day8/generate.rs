@@ -0,0 +1,19 @@
+//! Random forests.
+
+use aoc_common::rng::Rng;
+
+/// The width of a real input's forest.
+pub const SIZE: usize = 99;
+
+/// A `size` by `size` grid of tree heights.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let row: String = (0..size)
+                .map(|_| char::from(b'0' + rng.range(0..=9u8)))
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
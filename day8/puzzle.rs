@@ -1,71 +1,65 @@
-fn part_1(s: &str) -> usize {
-    let forest = s
-        .lines()
-        .map(|row| row.chars().collect::<Vec<char>>())
-        .collect::<Vec<_>>();
-    let mut visible = vec![vec![false; forest[0].len()]; forest.len()];
+pub mod generate;
 
-    let m = forest.len();
-    let n = forest[0].len();
+use aoc_common::grid::ORTHOGONAL;
+use aoc_common::{Grid, ParseError, Solution};
 
-    for i in 0..m {
-        for j in 0..n {
-            let h = forest[i][j];
-            // direction      up       left     right   down
-            for (di, dj) in [(-1, 0), (0, -1), (0, 1), (1, 0)] {
-                let (mut next_i, mut next_j) = ((i as i32 + di) as usize, (j as i32 + dj) as usize);
-                let mut blocked = false;
-                while !visible[i][j] && (0..m).contains(&next_i) && (0..n).contains(&next_j) {
-                    if forest[next_i][next_j] >= h {
-                        // next tree as tall or taller, note as blocked and stop looking in this
-                        // direction
-                        blocked = true;
-                        break;
-                    }
-                    (next_i, next_j) = ((next_i as i32 + di) as usize, (next_j as i32 + dj) as usize);
-                }
-                // not blocked in this direction - mark visible and stop looking
-                if !blocked { visible[i][j] = true;  break; }
-            }
-        }
-    }
+pub fn parse(s: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(s, |c| c.to_digit(10).map(|h| h as u8), "a tree height 0-9")
+}
+
+pub fn part_1(forest: &Grid<u8>) -> usize {
+    forest
+        .iter()
+        .filter(|&(pos, &h)| {
+            // visible if, in some direction, every tree up to the edge is shorter
+            ORTHOGONAL
+                .into_iter()
+                .any(|dir| forest.ray(pos, dir).all(|next| forest[next] < h))
+        })
+        .count()
+}
 
-    visible.iter().flatten().filter(|x| **x).count()
+pub fn part_2(forest: &Grid<u8>) -> usize {
+    forest
+        .iter()
+        .map(|(pos, &h)| {
+            ORTHOGONAL
+                .into_iter()
+                .map(|dir| {
+                    // count trees up to and including the first one as tall or taller
+                    let mut visible = 0;
+                    for next in forest.ray(pos, dir) {
+                        visible += 1;
+                        if forest[next] >= h {
+                            break;
+                        }
+                    }
+                    visible
+                })
+                .product()
+        })
+        .max()
+        .unwrap()
 }
 
-fn part_2(s: &str) -> usize {
-    let forest = s
-        .lines()
-        .map(|row| row.chars().collect::<Vec<char>>())
-        .collect::<Vec<_>>();
-    let mut score = vec![vec![1; forest[0].len()]; forest.len()];
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 8;
 
-    let m = forest.len();
-    let n = forest[0].len();
+    type Input = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    for i in 0..m {
-        for j in 0..n {
-            let h = forest[i][j];
-            // direction      up       left     right   down
-            for (di, dj) in [(-1, 0), (0, -1), (0, 1), (1, 0)] {
-                let (mut next_i, mut next_j) = ((i as i32 + di) as usize, (j as i32 + dj) as usize);
-                let mut visible = 0;
-                while (0..m).contains(&next_i) && (0..n).contains(&next_j) {
-                    visible += 1;
-                    if forest[next_i][next_j] >= h { break; }
-                    (next_i, next_j) = ((next_i as i32 + di) as usize, (next_j as i32 + dj) as usize);
-                }
-                score[i][j] *= visible;
-            }
-        }
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
     }
-    dbg!(*score.iter().flatten().max().unwrap()) as usize
-}
 
-fn main() {
-    assert!(dbg!(part_1(include_str!("test.input.txt"))) == 21);
-    println!("Part 1: {}", part_1(include_str!("input.txt")));
+    fn part_1(forest: &Self::Input) -> usize {
+        part_1(forest)
+    }
 
-    assert!(dbg!(part_2(include_str!("test.input.txt"))) == 8);
-    println!("Part 2: {}", part_2(include_str!("input.txt")));
+    fn part_2(forest: &Self::Input) -> usize {
+        part_2(forest)
+    }
 }